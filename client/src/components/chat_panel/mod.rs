@@ -0,0 +1,145 @@
+use futures::SinkExt;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::components::card::Card;
+use crate::components::icon::Icon;
+use crate::context::game_context::context::{GameContext, MsgSender};
+use crate::models::chat::{ChatContent, ChatMessage, EmoteKind};
+use crate::models::messages::ClientMessage;
+use crate::utils::resolve_text_color_class;
+
+// the server rejects longer messages
+const MAX_MESSAGE_LENGTH: usize = 200;
+
+fn send(sender: Option<MsgSender>, message: ClientMessage) {
+  spawn_local(async move {
+    if let Some(mut sender) = sender {
+      sender.0.send(message).await.ok();
+    }
+  });
+}
+
+fn render_message(message: &ChatMessage) -> Html {
+  let content = match &message.content {
+    ChatContent::Text(text) => {
+      html! { <span class="text-neutral-700 break-words">{ text.clone() }</span> }
+    }
+    ChatContent::Emote(emote) => html! { <span class="text-2xl">{ emote.symbol() }</span> },
+  };
+
+  html! {
+    <li class="flex items-baseline gap-2">
+      <span class={classes!(String::from("font-semibold"), resolve_text_color_class(&message.color))}>
+        { format!("{}:", message.name) }
+      </span>
+      { content }
+    </li>
+  }
+}
+
+/// collapsible chat with quick emotes, spectators can only read along
+#[function_component(ChatPanel)]
+pub fn chat_panel() -> Html {
+  let GameContext {
+    chat,
+    sender,
+    spectator,
+    ..
+  } = use_context::<GameContext>().expect("context not found");
+  let is_open = use_state(|| true);
+  let draft = use_state(String::new);
+
+  let on_toggle = {
+    let is_open = is_open.clone();
+    Callback::from(move |_| is_open.set(!*is_open))
+  };
+
+  let on_input = {
+    let draft = draft.clone();
+    Callback::from(move |e: InputEvent| {
+      let target = e.target();
+      let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+      if let Some(element) = input {
+        draft.set(element.value());
+      }
+    })
+  };
+
+  let on_submit = {
+    let draft = draft.clone();
+    let sender = sender.clone();
+    Callback::from(move |e: FocusEvent| {
+      e.prevent_default();
+      let text = draft.trim().to_owned();
+      if text.is_empty() {
+        return;
+      }
+      send(sender.clone(), ClientMessage::Chat(text));
+      draft.set(String::new());
+    })
+  };
+
+  let emotes = EmoteKind::all().iter().map(|emote| {
+    let emote = *emote;
+    let sender = sender.clone();
+    let onclick = Callback::from(move |_| send(sender.clone(), ClientMessage::Emote(emote)));
+    html! {
+      <button {onclick} class="text-2xl rounded hover:bg-neutral-100 p-1">{ emote.symbol() }</button>
+    }
+  });
+
+  let body = if *is_open {
+    html! {
+      <div class="flex flex-col gap-3 p-4 pt-0">
+        <ul class="flex flex-col gap-1 h-48 overflow-y-auto">
+          {
+            if chat.is_empty() {
+              html! { <li class="text-neutral-500">{"No messages yet"}</li> }
+            } else {
+              html! { <>{ for chat.iter().map(render_message) }</> }
+            }
+          }
+        </ul>
+        {
+          if spectator {
+            html! {}
+          } else {
+            html! {
+              <>
+                <div class="flex justify-between">{ for emotes }</div>
+                <form onsubmit={on_submit} class="flex gap-2">
+                  <input
+                    class="w-full border border-neutral-300 p-2 focus:border-primary-600 rounded shadow focus:outline-none"
+                    placeholder="Say something nice"
+                    maxlength={MAX_MESSAGE_LENGTH.to_string()}
+                    oninput={on_input}
+                    value={(*draft).clone()}
+                  />
+                  <button type="submit" class="rounded bg-primary-600 hover:brightness-90 text-white px-3 shadow-md">
+                    <Icon class="fas fa-paper-plane" />
+                  </button>
+                </form>
+              </>
+            }
+          }
+        }
+      </div>
+    }
+  } else {
+    html! {}
+  };
+
+  html! {
+    <Card>
+      <button onclick={on_toggle} class="w-full flex justify-between items-center p-4">
+        <span class="text-lg font-semibold text-neutral-700">{ format!("Chat ({})", chat.len()) }</span>
+        <Icon class={if *is_open { "fas fa-chevron-up" } else { "fas fa-chevron-down" }} />
+      </button>
+      { body }
+    </Card>
+  }
+}
@@ -0,0 +1,26 @@
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct CheckboxProps {
+  pub label: String,
+  pub checked: bool,
+  pub onchange: Callback<bool>,
+}
+
+#[function_component(Checkbox)]
+pub fn checkbox(props: &CheckboxProps) -> Html {
+  let CheckboxProps {
+    label,
+    checked,
+    onchange,
+  } = props.clone();
+
+  let onclick = Callback::from(move |_| onchange.emit(!checked));
+
+  html! {
+    <label class="flex items-center gap-3 font-semibold text-neutral-600 hover:cursor-pointer">
+      <input type="checkbox" class="h-5 w-5 accent-primary-600" {checked} {onclick} />
+      <span>{label}</span>
+    </label>
+  }
+}
@@ -1 +1,2 @@
+pub mod undo_dialog;
 pub mod winner_dialog;
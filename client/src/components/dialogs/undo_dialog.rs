@@ -0,0 +1,49 @@
+use yew::prelude::*;
+
+use crate::{
+  components::{button::Button, icon::Icon},
+  models::color::Color,
+  utils::resolve_text_color_class,
+};
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct UndoDialogProps {
+  pub close: Callback<()>,
+  pub color: Color,
+  /// name of the player who wants to take back their move
+  pub name: String,
+  pub answer: Callback<bool>,
+}
+
+#[function_component(UndoDialog)]
+pub fn undo_dialog(props: &UndoDialogProps) -> Html {
+  let UndoDialogProps {
+    close,
+    color,
+    name,
+    answer,
+  } = props.clone();
+
+  let respond = |accepted: bool| {
+    let close = close.clone();
+    let answer = answer.clone();
+    Callback::from(move |_| {
+      answer.emit(accepted);
+      close.emit(());
+    })
+  };
+
+  html! {
+    <div class="flex flex-col gap-6 text-neutral-600">
+      <span class="text-3xl font-bold">{ "Take back the move?" }</span>
+      <p class="text-xl">
+        <span class={classes!(String::from("font-semibold"), resolve_text_color_class(&color))}>{ name }</span>
+        { " would like to take back their last move." }
+      </p>
+      <div class="flex items-center gap-3">
+        <Button class="w-full" onclick={respond(true)} icon={html! { <Icon class="fas fa-check" /> }}>{"Allow"}</Button>
+        <Button class="w-full" bg_color="bg-red-700" onclick={respond(false)} icon={html! { <Icon class="fas fa-times" /> }}>{"Refuse"}</Button>
+      </div>
+    </div>
+  }
+}
@@ -1,30 +1,53 @@
 use yew::prelude::*;
 use yew_router::{history::History, hooks::use_history};
 
-use crate::{components::button::Button, routes::MainRoute};
+use crate::{
+  components::{button::Button, icon::Icon},
+  models::color::Color,
+  routes::MainRoute,
+  utils::resolve_text_color_class,
+};
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct WinnerDialogProps {
   pub close: Callback<()>,
-  pub winner_name: String,
+  /// players in finishing order (color, name), the first one is the winner
+  pub ranking: Vec<(Color, String)>,
 }
 
 #[function_component(WinnerDialog)]
 pub fn winner_dialog(props: &WinnerDialogProps) -> Html {
   let history = use_history().unwrap();
-  let WinnerDialogProps { close, winner_name } = props.clone();
+  let WinnerDialogProps { close, ranking } = props.clone();
 
   let onclick = Callback::from(move |_| {
     close.emit(());
     history.push(MainRoute::Home);
   });
 
+  let podium = ranking.iter().enumerate().map(|(index, (color, name))| {
+    let icon_class = match index {
+      0 => "fas fa-trophy text-yellow-500",
+      1 => "fas fa-medal text-neutral-400",
+      2 => "fas fa-medal text-amber-700",
+      _ => "fas fa-flag-checkered text-neutral-600",
+    };
+
+    html! {
+      <li class="flex items-center gap-4 rounded border border-neutral-300 p-3">
+        <Icon class={icon_class} />
+        <span class="font-bold">{ format!("{}.", index + 1) }</span>
+        <span class={classes!(String::from("font-semibold"), resolve_text_color_class(color))}>{ name.clone() }</span>
+      </li>
+    }
+  });
+
   html! {
     <div class="flex flex-col gap-6 text-neutral-600">
       <span class="text-3xl font-bold">{ "Game Over !" }</span>
-      <p class="mb-2 text-xl">{"The winner is:"}
-        <span class="ml-4 font-semibold text-green-600">{winner_name}</span>
-      </p>
+      <ol class="flex flex-col gap-2 text-xl">
+        { for podium }
+      </ol>
       <Button class="w-full" {onclick}>
         {"Go back to homepage"}
       </Button>
@@ -1,12 +1,10 @@
-use futures::SinkExt;
-use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
 use crate::components::icon::Icon;
 use crate::components::pawn::Pawn;
 use crate::context::game_context::context::GameContext;
 use crate::models::color::Color;
-use crate::models::messages::ClientMessage;
+use crate::models::piece::PieceLocation;
 use crate::utils::{resolve_bg_color_class, resolve_text_color_class};
 
 #[derive(PartialEq, Clone)]
@@ -38,8 +36,13 @@ pub fn field(props: &FieldProps) -> Html {
   } = props.clone();
   let GameContext {
     game,
-    sender,
     player_color,
+    spectator,
+    legal_moves,
+    preview,
+    set_preview,
+    selected,
+    play_piece,
     ..
   } = use_context::<GameContext>().expect("context not found");
 
@@ -51,41 +54,75 @@ pub fn field(props: &FieldProps) -> Html {
 
   let text_class = resolve_text_color_class(&color);
 
-  let pawn_color = if variant == FieldVariant::Home {
-    // TODO: add home pawns
+  let location = match variant {
+    FieldVariant::Home => PieceLocation::Home(position),
+    FieldVariant::Main => PieceLocation::Board(position % 52),
+  };
+
+  // only the own home column can be a destination of the previewed moves
+  let previewed_move = preview.and_then(|piece_id| {
+    legal_moves.iter().find(|piece_move| {
+      piece_move.piece_id() == piece_id
+        && piece_move.destination() == location
+        && (variant == FieldVariant::Main || color == player_color)
+    })
+  });
+  let destination_class: Option<String> = previewed_move.map(|piece_move| {
+    if piece_move.captures() {
+      "ring-4 ring-inset ring-red-500".into()
+    } else {
+      "ring-4 ring-inset ring-primary-400".into()
+    }
+  });
+
+  let piece = if variant == FieldVariant::Home {
     game
       .players
       .iter()
       .find(|player| player.color == color)
-      .and_then(|player| player.home.get(position).unwrap_or(&None).clone())
+      .and_then(|player| player.piece_in_home(position))
+      .cloned()
   } else {
-    game.fields.get(position)
-  };
-
-  let click_color = match variant {
-    FieldVariant::Home => Some(color),
-    FieldVariant::Main => None,
-  };
-
-  let onclick = {
-    Callback::from(move |_| {
-      let click_color = click_color.clone();
-      let sender = sender.clone();
-      spawn_local(async move {
-        if let Some(mut sender) = sender.clone() {
-          sender
-            .0
-            .send(ClientMessage::MoveFigure(position, click_color))
-            .await
-            .ok();
-        }
-      });
-    })
+    game.piece_on_board(position).cloned()
   };
+  let is_selected = piece
+    .as_ref()
+    .map(|piece| Some(piece.id) == selected)
+    .unwrap_or(false);
 
   let content = {
-    if let Some(color) = pawn_color {
-      html! { <Pawn color={color.clone()} onclick={(color == player_color).then(|| onclick)} /> }
+    if let Some(piece) = piece {
+      let piece_id = piece.id;
+      // with move confirmation the first click only selects the pawn
+      let onclick = Callback::from(move |_| play_piece.emit(piece_id));
+      let is_own = piece.color == player_color;
+      // illegal moves aren't sent at all, the server would only reject them
+      let can_move = !spectator
+        && is_own
+        && legal_moves
+          .iter()
+          .any(|piece_move| piece_move.piece_id() == piece_id);
+      let onhover = Callback::from(move |hovered: bool| {
+        set_preview.emit(hovered.then(|| piece_id));
+      });
+      let captured = previewed_move
+        .map(|piece_move| piece_move.captures())
+        .unwrap_or(false);
+      html! {
+        <>
+          <Pawn
+            color={piece.color}
+            onclick={can_move.then(|| onclick)}
+            highlighted={can_move}
+            onhover={can_move.then(|| onhover)}
+          />
+          {
+            if captured {
+              html! { <Icon class={classes!(String::from("fas fa-crosshairs absolute text-red-500 pointer-events-none"))} /> }
+            } else { html! {} }
+          }
+        </>
+      }
     } else if raw_position == 6 {
       html! { <Icon class={classes!(arrow_class)} /> }
     } else {
@@ -94,7 +131,7 @@ pub fn field(props: &FieldProps) -> Html {
   };
 
   html! {
-    <div class={classes!(String::from("relative border border-neutral-300 shadow-inner grid place-items-center"), bg_class, text_class)}>
+    <div class={classes!(String::from("relative border border-neutral-300 shadow-inner grid place-items-center"), bg_class, text_class, destination_class, is_selected.then(|| "ring-4 ring-inset ring-neutral-800"))}>
       {content}
     </div>
   }
@@ -0,0 +1,156 @@
+use reqwasm::http::Request;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::components::button::Button;
+use crate::components::icon::Icon;
+use crate::components::spinner::Spinner;
+use crate::context::toasts::context::{ToastOptions, ToastVariant, ToastsContext};
+use crate::models::game_summary::{GameListPage, GameStatus, GameSummary};
+use crate::routes::MainRoute;
+use crate::utils::get_host::http_string;
+use crate::utils::resolve_text_color_class;
+
+const PER_PAGE: u64 = 5;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct GameBrowserProps {
+  /// open lobbies can be joined, running games can be watched
+  #[prop_or(GameStatus::Lobby)]
+  pub status: GameStatus,
+}
+
+/// lists public games with the given status, newest first
+#[function_component(GameBrowser)]
+pub fn game_browser(props: &GameBrowserProps) -> Html {
+  let GameBrowserProps { status } = props.clone();
+  let watching = status == GameStatus::InProgress;
+  let history = use_history().unwrap();
+  let ToastsContext { open } = use_context::<ToastsContext>().expect("context not found");
+  let page = use_state(|| 0_u64);
+  let listing = use_state::<Option<GameListPage>, _>(|| None);
+
+  {
+    let listing = listing.clone();
+    use_effect_with_deps(
+      move |(page, status)| {
+        let page = *page;
+        let status = format!("{:?}", status);
+        spawn_local(async move {
+          let res = Request::get(
+            format!(
+              "{}/games?status={}&page={}&per_page={}",
+              http_string(),
+              status,
+              page,
+              PER_PAGE
+            )
+            .as_str(),
+          )
+          .send()
+          .await;
+
+          let resp = match res {
+            Ok(resp) if resp.ok() => resp,
+            _ => {
+              open.emit(ToastOptions {
+                message: "Couldn't load the game list".into(),
+                variant: ToastVariant::Error,
+              });
+              return;
+            }
+          };
+
+          if let Ok(games) = resp.json::<GameListPage>().await {
+            listing.set(Some(games));
+          }
+        });
+
+        || {}
+      },
+      (*page, status),
+    );
+  }
+
+  let on_previous = {
+    let page = page.clone();
+    Callback::from(move |_| page.set(page.saturating_sub(1)))
+  };
+
+  let on_next = {
+    let page = page.clone();
+    Callback::from(move |_| page.set(*page + 1))
+  };
+
+  let listing = match (*listing).clone() {
+    Some(listing) => listing,
+    None => {
+      return html! {
+        <div class="grid place-items-center p-6">
+          <Spinner />
+        </div>
+      }
+    }
+  };
+
+  let has_next = (listing.page + 1) * listing.per_page < listing.total;
+
+  let games = listing.games.iter().cloned().map(|game: GameSummary| {
+    let history = history.clone();
+    let id = game.id.clone();
+    let onclick = Callback::from(move |_| {
+      if watching {
+        history.push(MainRoute::GameWatch { id: id.clone() })
+      } else {
+        history.push(MainRoute::GameJoin { id: id.clone() })
+      }
+    });
+
+    let players = game.players.iter().map(|player| {
+      html! {
+        <span class={classes!(String::from("font-semibold"), resolve_text_color_class(&player.color))}>
+          { player.name.clone() }
+        </span>
+      }
+    });
+
+    html! {
+      <li key={game.id.clone()} class="w-full rounded border border-neutral-300 p-3 flex justify-between items-center gap-4">
+        <div class="flex flex-wrap items-center gap-3">
+          <span class="font-semibold text-neutral-600">{ format!("{} / 4", game.players.len()) }</span>
+          { for players }
+        </div>
+        {
+          if watching {
+            html! { <Button {onclick} icon={html! { <Icon class="fas fa-eye" /> }}>{"Watch"}</Button> }
+          } else {
+            html! { <Button {onclick} icon={html! { <Icon class="fas fa-sign-in-alt" /> }}>{"Join"}</Button> }
+          }
+        }
+      </li>
+    }
+  });
+
+  html! {
+    <div class="flex flex-col gap-3">
+      <p class="text-2xl font-bold text-neutral-800">{ if watching { "Watch a running game" } else { "Or join an open lobby" } }</p>
+      {
+        if listing.games.is_empty() {
+          html! {
+            <p class="text-lg font-semibold text-neutral-600">
+              { if watching { "There are no running games right now" } else { "There are no open lobbies right now" } }
+            </p>
+          }
+        } else {
+          html! { <ul class="flex flex-col gap-2">{ for games }</ul> }
+        }
+      }
+      <div class="flex items-center justify-between gap-3">
+        <Button onclick={on_previous} disabled={listing.page == 0} icon={html! { <Icon class="fas fa-chevron-left" /> }}>{"Previous"}</Button>
+        <span class="font-semibold text-neutral-600">{ format!("Page {}", listing.page + 1) }</span>
+        <Button onclick={on_next} disabled={!has_next} icon={html! { <Icon class="fas fa-chevron-right" /> }}>{"Next"}</Button>
+      </div>
+    </div>
+  }
+}
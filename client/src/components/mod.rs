@@ -2,12 +2,15 @@ pub mod board;
 pub mod board_middle;
 pub mod button;
 pub mod card;
+pub mod chat_panel;
+pub mod checkbox;
 pub mod content;
 pub mod copy_bar;
 pub mod dialogs;
 pub mod die;
 pub mod field;
 pub mod fields;
+pub mod game_browser;
 pub mod icon;
 pub mod outlined_item;
 pub mod pawn;
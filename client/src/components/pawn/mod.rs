@@ -9,15 +9,35 @@ pub struct PawnProps {
   pub color: Color,
   #[prop_or_default]
   pub onclick: Option<Callback<MouseEvent>>,
+  /// marks a pawn which can be moved with the current throw
+  #[prop_or(false)]
+  pub highlighted: bool,
+  /// emits true when the pointer enters the pawn and false when it leaves
+  #[prop_or_default]
+  pub onhover: Option<Callback<bool>>,
 }
 
 #[function_component(Pawn)]
 pub fn pawn(props: &PawnProps) -> Html {
-  let PawnProps { color, onclick } = props.clone();
+  let PawnProps {
+    color,
+    onclick,
+    highlighted,
+    onhover,
+  } = props.clone();
 
   let text_class = resolve_text_color_class(&color);
   let hover_anim: Option<String> = onclick.is_some().then(|| "hover:scale-110".into());
 
+  let highlight_class: Option<String> =
+    highlighted.then(|| "rounded-full ring-4 ring-white/80 animate-pulse".into());
+
+  let onmouseenter = onhover
+    .clone()
+    .map(|onhover| Callback::from(move |_: MouseEvent| onhover.emit(true)));
+  let onmouseleave =
+    onhover.map(|onhover| Callback::from(move |_: MouseEvent| onhover.emit(false)));
+
   let animation = css!(
     "
     animation: bounce 250ms cubic-bezier(.28,.49,.63,1.29);
@@ -33,7 +53,7 @@ pub fn pawn(props: &PawnProps) -> Html {
   );
 
   html! {
-    <button {onclick} class={classes!(animation, onclick.is_none().then(|| "cursor-default"))}>
+    <button {onclick} {onmouseenter} {onmouseleave} class={classes!(animation, highlight_class, onclick.is_none().then(|| "cursor-default"))}>
       <Icon class={classes!(String::from("fas text-xl sm:text-3xl md:text-4xl w-min fa-chess-pawn drop-shadow-md hue-rotate-15 saturate-50"), text_class, hover_anim, css!("text-shadow: 2px 2px 2px gray;"))} />
     </button>
   }
@@ -34,6 +34,8 @@ pub fn player(props: &PlayerProps) -> Html {
     game,
     dice_info,
     sender,
+    spectator,
+    last_failure,
     ..
   } = use_context::<GameContext>().expect("context not found");
   let die_info = dice_info.get(&color).expect("die info not set for player");
@@ -73,9 +75,27 @@ pub fn player(props: &PlayerProps) -> Html {
     );
   }
 
+  // the roll was rejected, let the player try again right away
+  {
+    let is_rolling = is_rolling.clone();
+    use_effect_with_deps(
+      move |last_failure| {
+        let roll_failed = last_failure
+          .as_ref()
+          .map(|failure| failure.command == Some(ClientMessage::ThrowDice))
+          .unwrap_or(false);
+        if roll_failed {
+          is_rolling.set(false);
+        }
+        || {}
+      },
+      last_failure,
+    );
+  }
+
   let disabled = !die_info.can_roll || *is_rolling || player_color != color;
 
-  let button = if player_color == color {
+  let button = if !spectator && player_color == color {
     html! { <Button {icon} onclick={roll} { disabled }>{"Roll the die"}</Button> }
   } else {
     html! {}
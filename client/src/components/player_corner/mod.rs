@@ -1,11 +1,9 @@
-use futures::SinkExt;
-use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
 use crate::components::pawn::Pawn;
 use crate::context::game_context::context::GameContext;
 use crate::models::color::Color;
-use crate::models::messages::ClientMessage;
+use crate::models::game_move::Move;
 use crate::utils::{clamp, resolve_bg_color_class};
 
 #[derive(Properties, PartialEq, Clone)]
@@ -19,7 +17,10 @@ pub fn player_corner(props: &PlayerCornerProps) -> Html {
   let GameContext {
     game,
     player_color,
-    sender,
+    spectator,
+    legal_moves,
+    set_preview,
+    play_piece,
     ..
   } = use_context::<GameContext>().expect("context not found");
 
@@ -27,27 +28,25 @@ pub fn player_corner(props: &PlayerCornerProps) -> Html {
     .players
     .iter()
     .find(|player| player.color == color)
-    .map(|player| player.pawns_at_start)
+    .map(|player| player.pawns_at_start())
     .unwrap_or(0);
 
   let pawn_count = clamp(pawn_count, 0, 4);
 
-  let onclick = {
-    Callback::from(move |_| {
-      let sender = sender.clone();
-      spawn_local(async move {
-        if let Some(mut sender) = sender.clone() {
-          sender.0.send(ClientMessage::PromotePiece).await.ok();
-        };
-      });
-    })
-  };
+  let promotion = legal_moves
+    .iter()
+    .find(|piece_move| matches!(piece_move, Move::Promote { .. }))
+    .map(|piece_move| piece_move.piece_id());
+  let can_promote = !spectator && color == player_color && promotion.is_some();
+
+  // with move confirmation the first click only selects the promoted pawn
+  let onclick = promotion
+    .filter(|_| can_promote)
+    .map(|piece_id| Callback::from(move |_| play_piece.emit(piece_id)));
 
-  let onclick = if color == player_color {
-    Some(onclick)
-  } else {
-    None
-  };
+  // all pawns at start are equal, the previewed one is the one the server would promote
+  let onhover = can_promote
+    .then(|| Callback::from(move |hovered: bool| set_preview.emit(promotion.filter(|_| hovered))));
 
   let color_class = resolve_bg_color_class(&color);
   html! {
@@ -56,7 +55,7 @@ pub fn player_corner(props: &PlayerCornerProps) -> Html {
         {
           (0..pawn_count).map(|index| html! {
             <div class="grid place-items-center h-full w-full">
-              <Pawn onclick={onclick.clone()} key={index} color={color.clone()}/>
+              <Pawn onclick={onclick.clone()} onhover={onhover.clone()} highlighted={can_promote} key={index} color={color.clone()}/>
             </div>
           }).collect::<Vec<Html>>()
         }
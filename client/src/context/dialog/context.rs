@@ -10,4 +10,6 @@ pub struct OpenDialogOptions {
 #[derive(Clone, PartialEq)]
 pub struct DialogContext {
   pub open: Callback<OpenDialogOptions>,
+  /// closes the dialog when it's no longer relevant, e.g. the question was answered elsewhere
+  pub close: Callback<()>,
 }
@@ -20,7 +20,10 @@ pub fn dialog_provider(props: &DialogProviderProps) -> Html {
     is_open,
   } = use_dialog();
 
-  let context = DialogContext { open };
+  let context = DialogContext {
+    open,
+    close: close.clone(),
+  };
   let content_html = content(close);
 
   let backdrop_class = css!("background-color: rgba(0, 0, 0, .5);");
@@ -1,10 +1,13 @@
 use std::collections::HashMap;
 
 use crate::models::{
+  chat::ChatMessage,
   color::Color,
   die_info::DieInfo,
   game::Game,
+  game_move::Move,
   messages::{ClientMessage, ServerMessage},
+  server_error::ServerError,
 };
 use futures::channel::mpsc::Sender;
 use yew::Callback;
@@ -14,11 +17,36 @@ pub struct GameContext {
   pub game: Game,
   pub player_color: Color,
   pub player_count: u32,
+  pub spectator: bool,
+  pub spectator_count: usize,
   pub subscribe: Callback<Callback<ServerMessage>>,
   pub sender: Option<MsgSender>,
   // pub players: HashMap<Color, Player>,
   pub current_player: Color,
   pub dice_info: HashMap<Color, DieInfo>,
+  pub chat: Vec<ChatMessage>,
+  pub last_failure: Option<FailedRequest>,
+  pub legal_moves: Vec<Move>,
+  /// id of the piece whose moves are previewed on the board
+  pub preview: Option<usize>,
+  pub set_preview: Callback<Option<usize>>,
+  /// moves are sent only after the selected piece is confirmed, see `utils::settings`
+  pub confirm_moves: bool,
+  pub set_confirm_moves: Callback<bool>,
+  /// piece waiting for the confirmation of its move
+  pub selected: Option<usize>,
+  pub select: Callback<Option<usize>>,
+  /// sends the move of the piece, or just selects it when moves have to be confirmed
+  pub play_piece: Callback<usize>,
+  /// player asking the others to take back their last move
+  pub undo_request: Option<Color>,
+}
+
+/// error sent by the server together with the command which caused it
+#[derive(Clone, Debug, PartialEq)]
+pub struct FailedRequest {
+  pub error: ServerError,
+  pub command: Option<ClientMessage>,
 }
 
 #[derive(Clone, Debug)]
@@ -1,14 +1,54 @@
+use engine::utils::enums::RoundPhase;
 use std::{collections::HashMap, rc::Rc};
 use yew::Reducible;
 
-use crate::models::{color::Color, die_info::DieInfo, game::Game, messages::ServerMessage};
+use crate::models::{
+  chat::ChatMessage, color::Color, die_info::DieInfo, game::Game, game_move::Move,
+  messages::ServerMessage, piece::PieceLocation,
+};
+
+/// older messages are dropped from the chat panel
+const CHAT_HISTORY_SIZE: usize = 50;
+
+/// how long a moving pawn stays on every field it passes, bots on the server wait just as long
+pub const ANIMATION_STEP_MS: u32 = 250;
+
+pub enum GameAction {
+  Message(ServerMessage),
+  /// moves the animated pawns by one field
+  AnimationStep,
+}
+
+impl From<ServerMessage> for GameAction {
+  fn from(message: ServerMessage) -> Self {
+    GameAction::Message(message)
+  }
+}
+
+/// fields a pawn walks over between two game updates
+#[derive(Clone, Debug, PartialEq)]
+pub struct PieceAnimation {
+  pub piece_id: usize,
+  pub path: Vec<PieceLocation>,
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct GameState {
   pub game: Game,
   pub player_color: Color,
   pub player_count: u32,
+  pub spectator_count: usize,
   pub dice_info: HashMap<Color, DieInfo>,
+  pub chat: Vec<ChatMessage>,
+  /// set when a GameDelta couldn't be applied, cleared by the next full game
+  pub needs_resync: bool,
+  /// moves offered for the current throw, every game change makes them stale
+  pub legal_moves: Vec<Move>,
+  /// pawns which haven't reached their location in `game` yet
+  pub animations: Vec<PieceAnimation>,
+  pub animation_frame: usize,
+  /// player waiting for the others to agree with taking back their last move
+  pub undo_request: Option<Color>,
 }
 
 impl Default for GameState {
@@ -17,6 +57,7 @@ impl Default for GameState {
       game: Game::new(),
       player_color: Color::Green,
       player_count: 0,
+      spectator_count: 0,
       dice_info: [
         (Color::Green, DieInfo::new()),
         (Color::Yellow, DieInfo::new()),
@@ -26,15 +67,102 @@ impl Default for GameState {
       .iter()
       .cloned()
       .collect::<HashMap<_, _>>(),
+      chat: Vec::new(),
+      needs_resync: false,
+      legal_moves: Vec::new(),
+      animations: Vec::new(),
+      animation_frame: 0,
+      undo_request: None,
+    }
+  }
+}
+
+impl GameState {
+  pub fn is_animating(&self) -> bool {
+    !self.animations.is_empty()
+  }
+
+  /// the game with the animated pawns on their current field instead of their final one
+  pub fn displayed_game(&self) -> Game {
+    let mut game = self.game.clone();
+    for animation in &self.animations {
+      let frame = self.animation_frame.min(animation.path.len() - 1);
+      let piece = game
+        .players
+        .iter_mut()
+        .flat_map(|player| player.pieces.iter_mut())
+        .find(|piece| piece.id == animation.piece_id);
+      if let Some(piece) = piece {
+        piece.location = animation.path[frame];
+      }
+    }
+    game
+  }
+
+  /// captured pawns wait until the capturing one arrives and then jump back to start
+  fn animations_between(previous: &Game, game: &Game) -> Vec<PieceAnimation> {
+    let mut animations: Vec<PieceAnimation> = game
+      .pieces()
+      .filter_map(|piece| {
+        let before = previous
+          .pieces()
+          .find(|before| before.id == piece.id && before.location != piece.location)?;
+        Some(PieceAnimation {
+          piece_id: piece.id,
+          path: before.path_to(piece.location),
+        })
+      })
+      .collect();
+
+    let longest = animations
+      .iter()
+      .map(|animation| animation.path.len())
+      .max()
+      .unwrap_or(0);
+    for animation in animations.iter_mut() {
+      if animation.path.last() == Some(&PieceLocation::Start) {
+        let from = animation.path[0];
+        animation.path = vec![from; longest - 1];
+        animation.path.push(PieceLocation::Start);
+      }
+    }
+    animations
+  }
+
+  fn next_animation_frame(self: Rc<Self>) -> Rc<Self> {
+    let animation_frame = self.animation_frame + 1;
+    let finished = self
+      .animations
+      .iter()
+      .all(|animation| animation_frame + 1 >= animation.path.len());
+
+    Self {
+      animations: if finished {
+        Vec::new()
+      } else {
+        self.animations.clone()
+      },
+      animation_frame: if finished { 0 } else { animation_frame },
+      ..(*self).clone()
     }
+    .into()
   }
 }
 
 impl Reducible for GameState {
-  type Action = ServerMessage;
+  type Action = GameAction;
 
   fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
     match action {
+      GameAction::Message(message) => self.apply_message(message),
+      GameAction::AnimationStep => self.next_animation_frame(),
+    }
+  }
+}
+
+impl GameState {
+  fn apply_message(self: Rc<Self>, message: ServerMessage) -> Rc<Self> {
+    match message {
       ServerMessage::DiceValue(number, can_roll) => {
         let mut new_dice_info = self.dice_info.clone();
         new_dice_info.insert(
@@ -44,14 +172,18 @@ impl Reducible for GameState {
 
         Self {
           dice_info: new_dice_info,
+          // the move can't be taken back once the dice are thrown
+          undo_request: None,
           ..(*self).clone()
         }
         .into()
       }
       ServerMessage::GameUpdate(game) | ServerMessage::GameStarted(game) => {
         let current_player = game.current_player.clone();
+        // a move taken back returns the player to moving with the same dice
+        let rolling = game.round_phase == RoundPhase::Rolling;
         let dice_info = self.dice_info.iter().map(|(color, die_info)| {
-          let can_roll = current_player == *color;
+          let can_roll = rolling && current_player == *color;
           let die_info = DieInfo {
             can_roll,
             ..die_info.clone()
@@ -60,13 +192,46 @@ impl Reducible for GameState {
         });
 
         Self {
+          animations: Self::animations_between(&self.game, &game),
+          animation_frame: 0,
           game,
           dice_info: dice_info.collect(),
+          needs_resync: false,
+          legal_moves: Vec::new(),
           ..(*self).clone()
         }
         .into()
       }
-      ServerMessage::ConnectResponse(game, player_color) => {
+      ServerMessage::GameDelta(delta) => {
+        let mut game = self.game.clone();
+        match game.apply_delta(delta) {
+          true => self.apply_message(ServerMessage::GameUpdate(game)),
+          false => Self {
+            needs_resync: true,
+            ..(*self).clone()
+          }
+          .into(),
+        }
+      }
+      ServerMessage::LegalMoves(legal_moves) => Self {
+        legal_moves,
+        ..(*self).clone()
+      }
+      .into(),
+      ServerMessage::LegalMovesPerDie(die_moves) => Self {
+        legal_moves: die_moves
+          .into_iter()
+          .flat_map(|die_moves| die_moves.moves)
+          .collect(),
+        ..(*self).clone()
+      }
+      .into(),
+      ServerMessage::SpectateResponse(game, chat) => Rc::new(Self {
+        chat,
+        ..(*self).clone()
+      })
+      .apply_message(ServerMessage::GameUpdate(game)),
+      ServerMessage::ConnectResponse(game, player_color, chat) => {
         let current_player = game.current_player.clone();
         let dice_info = self.dice_info.iter().map(|(color, die_info)| {
           let can_roll = current_player == *color;
@@ -80,11 +245,44 @@ impl Reducible for GameState {
         Self {
           game,
           player_color,
+          chat,
           dice_info: dice_info.collect(),
+          needs_resync: false,
+          legal_moves: Vec::new(),
+          animations: Vec::new(),
+          animation_frame: 0,
+          ..(*self).clone()
+        }
+        .into()
+      }
+      ServerMessage::Chat(message) => {
+        let mut chat = self.chat.clone();
+        chat.push(message);
+        if chat.len() > CHAT_HISTORY_SIZE {
+          chat.remove(0);
+        }
+
+        Self {
+          chat,
           ..(*self).clone()
         }
         .into()
       }
+      ServerMessage::UndoRequested(color) => Self {
+        undo_request: Some(color),
+        ..(*self).clone()
+      }
+      .into(),
+      ServerMessage::UndoResolved(_) => Self {
+        undo_request: None,
+        ..(*self).clone()
+      }
+      .into(),
+      ServerMessage::SpectatorCountChange(spectator_count) => Self {
+        spectator_count,
+        ..(*self).clone()
+      }
+      .into(),
       _ => self,
     }
   }
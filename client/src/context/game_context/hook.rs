@@ -1,29 +1,62 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
 use futures::channel::mpsc;
 use futures::{SinkExt, StreamExt};
 use gloo::console::log;
 use gloo::storage::{SessionStorage, Storage};
+use gloo::timers::callback::Timeout;
 use reqwasm::websocket::futures::WebSocket;
 use reqwasm::websocket::Message;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
-use super::context::{GameContext, MsgSender};
-use super::game_reducer::GameState;
+use super::context::{FailedRequest, GameContext, MsgSender};
+use super::game_reducer::{GameAction, GameState, ANIMATION_STEP_MS};
+use super::local_game::{LocalGame, LocalSetup};
 use crate::context::toasts::context::{ToastOptions, ToastVariant, ToastsContext};
-use crate::models::messages::{ClientMessage, ServerMessage};
-use crate::utils::get_host::WS_STRING;
+use crate::models::color::Color;
+use crate::models::game_move::Move;
+use crate::models::messages::{ClientMessage, ClientRequest, ServerMessage};
+use crate::utils::error_message::error_message;
+use crate::utils::get_host::ws_string;
+use crate::utils::settings::{confirm_moves_enabled, set_confirm_moves};
+use crate::utils::wire_format::{decode, encode, WIRE_FORMAT};
+
+/// how many sent commands are remembered to match them with errors
+const PENDING_REQUESTS: usize = 32;
+/// pause before a bot of a local game throws, the previous move has been animated by then
+const LOCAL_BOT_DELAY_MS: u32 = 1000;
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct UseGameProps {
   pub game_id: String,
+  pub spectator: bool,
+  /// the game is played on this screen only, without the server
+  pub local: Option<LocalSetup>,
 }
 
 pub fn use_game(props: &UseGameProps) -> GameContext {
   let ToastsContext { open } = use_context::<ToastsContext>().expect("context not found");
   let game_state = use_reducer(GameState::default);
   let sender = use_state(|| None);
+  // spectators aren't given the sender, but they still ask for resyncs
+  let sync_sender = use_state::<Option<MsgSender>, _>(|| None);
   let game_id = props.game_id.clone();
+  let local = props.local.clone();
+  // a local game without humans can only be watched
+  let spectator = props.spectator
+    || local
+      .as_ref()
+      .map(|setup| setup.humans.is_empty())
+      .unwrap_or(false);
+  let local_game = use_mut_ref::<Option<LocalGame>, _>(|| None);
   let event_handler = use_state::<Option<Callback<ServerMessage>>, _>(|| None);
+  let last_failure = use_state::<Option<FailedRequest>, _>(|| None);
+  let preview = use_state::<Option<usize>, _>(|| None);
+  let confirm_moves = use_state(confirm_moves_enabled);
+  let selected = use_state::<Option<usize>, _>(|| None);
 
   let handle_message = {
     let game_state = game_state.clone();
@@ -47,58 +80,153 @@ pub fn use_game(props: &UseGameProps) -> GameContext {
             variant: ToastVariant::Success,
           });
         }
-        ServerMessage::Error(message) => {
+        ServerMessage::Error(error) => {
           open.emit(ToastOptions {
-            message,
+            message: error_message(&error.code),
             variant: ToastVariant::Error,
           });
         }
+        ServerMessage::UndoResolved(taken_back) => {
+          open.emit(ToastOptions {
+            message: match taken_back {
+              true => "The last move was taken back".into(),
+              false => "The other players didn't agree with the undo".into(),
+            },
+            variant: ToastVariant::Warning,
+          });
+        }
         _ => {}
       };
-      game_state.dispatch(message);
+      game_state.dispatch(message.into());
     })
   };
 
+  let handle_failure = {
+    let last_failure = last_failure.clone();
+    Callback::from(move |failure: FailedRequest| last_failure.set(Some(failure)))
+  };
+
   {
     let sender = sender.clone();
+    let sync_sender = sync_sender.clone();
     let event_handler = event_handler.clone();
+    let handle_message = handle_message.clone();
+    let local = local.clone();
+    let local_game = local_game.clone();
     use_effect_with_deps::<_, Box<dyn FnOnce()>, _>(
       move |callback| {
         let callback = (**callback).clone();
         let handle_message = handle_message.clone();
-        let player_id: String = SessionStorage::get("player_id").unwrap();
-        let ws = WebSocket::open(
+        let handle_failure = handle_failure.clone();
+
+        // local games don't connect anywhere, the engine answers the commands right away
+        if let Some(setup) = local {
+          if local_game.borrow().is_none() {
+            let game = LocalGame::new(&setup);
+            handle_message.emit(ServerMessage::GameStarted(game.game()));
+            *local_game.borrow_mut() = Some(game);
+          }
+          let (tx, mut rx) = mpsc::channel::<ClientMessage>(1000);
+          sender.set(Some(MsgSender(tx)));
+          spawn_local(async move {
+            while let Some(msg) = rx.next().await {
+              let messages = local_game
+                .borrow_mut()
+                .as_mut()
+                .map(|game| game.handle(msg))
+                .unwrap_or_default();
+              for message in messages {
+                handle_message.emit(message.clone());
+                if let Some(callback) = callback.clone() {
+                  callback.emit(message);
+                };
+              }
+            }
+          });
+          return Box::new(|| {});
+        }
+
+        // sent commands by their request id, oldest first
+        let pending = Rc::new(RefCell::new(VecDeque::<(String, ClientMessage)>::new()));
+        // spectators don't have a player id and never send any commands
+        let url = if spectator {
+          format!(
+            "{}/games/spectate/{}?encoding={}",
+            ws_string(),
+            game_id,
+            WIRE_FORMAT
+          )
+        } else {
+          let player_id: String = SessionStorage::get("player_id").unwrap();
           format!(
-            "{}/games/websocket/{}/{}",
-            WS_STRING, game_id, player_id
+            "{}/games/websocket/{}/{}?encoding={}",
+            ws_string(),
+            game_id,
+            player_id,
+            WIRE_FORMAT
           )
-          .as_str(),
-        )
-        .unwrap();
+        };
+        let ws = WebSocket::open(url.as_str()).unwrap();
 
         let (mut write, mut read) = ws.split();
         let (tx, mut rx) = mpsc::channel::<ClientMessage>(1000);
-        sender.set(Some(MsgSender(tx)));
+        if !spectator {
+          sender.set(Some(MsgSender(tx.clone())));
+        }
+        sync_sender.set(Some(MsgSender(tx)));
 
-        spawn_local(async move {
-          // TODO: handle errors as well
-          while let Some(Ok(Message::Text(text))) = read.next().await {
-            log!(text.clone());
-            if let Ok(message) = serde_json::from_str::<ServerMessage>(text.as_str()) {
-              handle_message.emit(message.clone());
-              if let Some(callback) = callback.clone() {
-                callback.emit(message.clone());
-              };
-            } else {
-              log!("Parsing of message failed:\n", text);
+        {
+          let pending = pending.clone();
+          spawn_local(async move {
+            // TODO: handle errors as well
+            while let Some(Ok(frame)) = read.next().await {
+              if let Message::Text(text) = &frame {
+                log!(text.clone());
+              }
+              if let Some(message) = decode::<ServerMessage>(&frame) {
+                if let ServerMessage::Error(error) = &message {
+                  let mut pending = pending.borrow_mut();
+                  let index = pending
+                    .iter()
+                    .position(|(request_id, _)| error.request_id.as_ref() == Some(request_id));
+                  let command = index
+                    .and_then(|index| pending.remove(index))
+                    .map(|(_, command)| command);
+                  handle_failure.emit(FailedRequest {
+                    error: error.clone(),
+                    command,
+                  });
+                }
+                handle_message.emit(message.clone());
+                if let Some(callback) = callback.clone() {
+                  callback.emit(message.clone());
+                };
+              } else {
+                log!("Parsing of message failed:\n", format!("{:?}", frame));
+              }
             }
-          }
-        });
+          });
+        }
 
         spawn_local(async move {
+          let mut next_request_id: u32 = 0;
           while let Some(msg) = rx.next().await {
-            let json = serde_json::to_string(&msg).unwrap();
-            write.send(Message::Text(json)).await.unwrap();
+            let request_id = next_request_id.to_string();
+            next_request_id += 1;
+
+            {
+              let mut pending = pending.borrow_mut();
+              pending.push_back((request_id.clone(), msg.clone()));
+              if pending.len() > PENDING_REQUESTS {
+                pending.pop_front();
+              }
+            }
+
+            let request = ClientRequest::Tagged {
+              request_id,
+              message: msg,
+            };
+            write.send(encode(&request)).await.unwrap();
           }
         });
 
@@ -108,6 +236,143 @@ pub fn use_game(props: &UseGameProps) -> GameContext {
     );
   }
 
+  {
+    let sync_sender = (*sync_sender).clone();
+    use_effect_with_deps(
+      move |needs_resync| {
+        if let (true, Some(mut sender)) = (*needs_resync, sync_sender) {
+          spawn_local(async move {
+            sender.0.send(ClientMessage::RequestSync).await.ok();
+          });
+        }
+        || {}
+      },
+      game_state.needs_resync,
+    );
+  }
+
+  // pawns walk one field per step until they reach their place in the latest game
+  {
+    let game_state = game_state.clone();
+    let animation_key = (game_state.game.sequence, game_state.animation_frame);
+    use_effect_with_deps(
+      move |_| {
+        let timeout = game_state.is_animating().then(|| {
+          Timeout::new(ANIMATION_STEP_MS, move || {
+            game_state.dispatch(GameAction::AnimationStep)
+          })
+        });
+        move || drop(timeout)
+      },
+      animation_key,
+    );
+  }
+
+  // bots of local games wait until the board has shown the previous move
+  {
+    let local_game = local_game.clone();
+    let bot_key = (game_state.game.sequence, game_state.is_animating());
+    use_effect_with_deps(
+      move |(_, is_animating)| {
+        let is_bot_turn = !*is_animating
+          && local_game
+            .borrow()
+            .as_ref()
+            .map(|game| game.is_bot_turn())
+            .unwrap_or(false);
+        let timeout = is_bot_turn.then(|| {
+          Timeout::new(LOCAL_BOT_DELAY_MS, move || {
+            let messages = local_game
+              .borrow_mut()
+              .as_mut()
+              .map(|game| game.play_bot_turn())
+              .unwrap_or_default();
+            for message in messages {
+              handle_message.emit(message);
+            }
+          })
+        });
+        move || drop(timeout)
+      },
+      bot_key,
+    );
+  }
+
+  // on a shared screen the player on turn is the one playing
+  let player_color = match &local {
+    Some(setup) if setup.humans.contains(&game_state.game.current_player) => {
+      game_state.game.current_player.clone()
+    }
+    Some(setup) => setup.humans.first().cloned().unwrap_or(Color::Green),
+    None => game_state.player_color.clone(),
+  };
+
+  let set_preview = {
+    let preview = preview.clone();
+    Callback::from(move |piece_id: Option<usize>| preview.set(piece_id))
+  };
+
+  // a selection belongs to the position it was made in
+  {
+    let selected = selected.clone();
+    use_effect_with_deps(
+      move |_| {
+        selected.set(None);
+        || {}
+      },
+      game_state.game.sequence,
+    );
+  }
+
+  let select = {
+    let selected = selected.clone();
+    Callback::from(move |piece_id: Option<usize>| selected.set(piece_id))
+  };
+
+  let set_confirm_moves = {
+    let confirm_moves = confirm_moves.clone();
+    let selected = selected.clone();
+    Callback::from(move |enabled: bool| {
+      set_confirm_moves(enabled);
+      confirm_moves.set(enabled);
+      selected.set(None);
+    })
+  };
+
+  let play_piece = {
+    let sender = (*sender).clone();
+    let legal_moves = game_state.legal_moves.clone();
+    let confirm_moves = *confirm_moves;
+    let selected = selected.clone();
+    Callback::from(move |piece_id: usize| {
+      if confirm_moves && *selected != Some(piece_id) {
+        selected.set(Some(piece_id));
+        return;
+      }
+      selected.set(None);
+      let promotes = legal_moves.iter().any(|piece_move| {
+        piece_move.piece_id() == piece_id && matches!(piece_move, Move::Promote { .. })
+      });
+      let message = match promotes {
+        true => ClientMessage::PromotePiece,
+        false => ClientMessage::MoveFigure(piece_id),
+      };
+      let sender = sender.clone();
+      spawn_local(async move {
+        if let Some(mut sender) = sender {
+          sender.0.send(message).await.ok();
+        }
+      });
+    })
+  };
+
+  let has_moves = |piece_id: &usize| {
+    game_state
+      .legal_moves
+      .iter()
+      .any(|piece_move| piece_move.piece_id() == *piece_id)
+  };
+
   let subscribe = {
     Callback::from(move |function: Callback<ServerMessage>| {
       event_handler.set(Some(function));
@@ -115,12 +380,27 @@ pub fn use_game(props: &UseGameProps) -> GameContext {
   };
 
   GameContext {
-    game: game_state.game.clone(),
-    player_color: game_state.player_color.clone(),
+    game: game_state.displayed_game(),
+    player_color,
     player_count: 0,
+    spectator,
+    spectator_count: game_state.spectator_count,
     subscribe,
     sender: (*sender).clone(),
     current_player: game_state.game.current_player.clone(),
     dice_info: game_state.dice_info.clone(),
+    chat: game_state.chat.clone(),
+    last_failure: (*last_failure).clone(),
+    legal_moves: game_state.legal_moves.clone(),
+    // the previewed piece may have lost its moves since it was hovered,
+    // the selected one stays previewed until its move is confirmed
+    preview: (*selected).or(*preview).filter(has_moves),
+    set_preview,
+    confirm_moves: *confirm_moves,
+    set_confirm_moves,
+    selected: (*selected).filter(has_moves),
+    select,
+    play_piece,
+    undo_request: game_state.undo_request.clone(),
   }
 }
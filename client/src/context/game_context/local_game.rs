@@ -0,0 +1,211 @@
+use engine::models::position::Position;
+use engine::utils::{
+  bot::{choose_bot_move, create_bot_name},
+  dice::get_dice_value,
+  enums::{MoveResult, MoveType, RoundPhase},
+  game::play_round,
+};
+
+use crate::models::{
+  color::Color,
+  game::Game,
+  messages::{ClientMessage, ServerMessage},
+  player::Player,
+  ruleset::Ruleset,
+  server_error::{ErrorCode, ServerError},
+};
+
+/// colors played on this screen, the remaining ones are played by bots
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalSetup {
+  pub humans: Vec<Color>,
+  pub ruleset: Ruleset,
+  /// the game starts from this position instead of the initial one
+  pub position: Option<Position>,
+}
+
+/// the rules engine running in the browser, it answers commands with the messages
+/// the server would send, so the game can be played without any connection
+pub struct LocalGame {
+  game: Game,
+  /// the game before the last move of a human, everyone plays on this screen so it's taken back right away
+  previous: Option<Game>,
+}
+
+fn error(code: ErrorCode) -> ServerMessage {
+  ServerMessage::Error(ServerError::new(code, None))
+}
+
+impl LocalGame {
+  pub fn new(setup: &LocalSetup) -> Self {
+    let mut game = Game::new();
+    game.ruleset = setup.ruleset.clone();
+    game.players = Color::ordered()
+      .into_iter()
+      .enumerate()
+      .map(|(index, color)| {
+        let is_human = setup.humans.contains(&color);
+        let name = match is_human {
+          true => format!("Player {}", index + 1),
+          false => create_bot_name(),
+        };
+        Player::new(index.to_string(), name, color, !is_human)
+      })
+      .collect();
+    if let Some(position) = &setup.position {
+      game.set_up(position);
+    }
+    game.started = true;
+    LocalGame {
+      game,
+      previous: None,
+    }
+  }
+
+  pub fn game(&self) -> Game {
+    self.game.clone()
+  }
+
+  pub fn is_bot_turn(&self) -> bool {
+    self.game.winner.is_none() && self.game.is_current_player_ai()
+  }
+
+  pub fn handle(&mut self, message: ClientMessage) -> Vec<ServerMessage> {
+    // the move which passed the turn to a bot can be taken back before the bot plays
+    if message == ClientMessage::RequestUndo {
+      return self.undo();
+    }
+    if self.is_bot_turn() {
+      return vec![error(ErrorCode::NotYourTurn)];
+    }
+    match message {
+      ClientMessage::ThrowDice => self.throw_dice(),
+      ClientMessage::MoveFigure(piece_id) => self.play(MoveType::Move(piece_id)),
+      ClientMessage::MoveFigureWithDie(piece_id, die_index) => {
+        self.play(MoveType::MoveWithDie(piece_id, die_index))
+      }
+      ClientMessage::PromotePiece => self.play(MoveType::Promote),
+      ClientMessage::RequestSync => vec![ServerMessage::GameUpdate(self.game())],
+      // local games start right away and have no chat
+      _ => Vec::new(),
+    }
+  }
+
+  /// one throw of the bot, or one move with a die when the dice are split
+  pub fn play_bot_turn(&mut self) -> Vec<ServerMessage> {
+    let mut messages = Vec::new();
+    self.previous = None;
+    if self.game.round_phase == RoundPhase::Rolling {
+      // a 6 is followed by another throw, at most three throws in total
+      while self.game.dice_throws.len() < 3
+        && self.game.dice_throws.last().copied().unwrap_or(6) == 6
+      {
+        let roll = get_dice_value();
+        self.game.dice_throws.push(roll);
+        messages.push(ServerMessage::DiceValue(roll, false));
+      }
+      if self.game.dice_throws.iter().sum::<usize>() == 18 {
+        messages.extend(self.skip_player());
+        return messages;
+      }
+      self.game.round_phase = RoundPhase::Moving;
+    }
+
+    let move_type = match self.game.ruleset.split_dice {
+      true => self
+        .game
+        .dice_throws
+        .iter()
+        .enumerate()
+        .find_map(|(die_index, &value)| {
+          choose_bot_move(&self.game, value)
+            .map(|piece_move| MoveType::MoveWithDie(piece_move.piece_id(), die_index))
+        }),
+      false => choose_bot_move(&self.game, self.game.dice_throws.iter().sum())
+        .map(|piece_move| MoveType::Move(piece_move.piece_id())),
+    };
+    match move_type {
+      Some(move_type) => messages.extend(self.play(move_type)),
+      None => messages.extend(self.skip_player()),
+    }
+    self.previous = None;
+    messages
+  }
+
+  fn throw_dice(&mut self) -> Vec<ServerMessage> {
+    if self.game.round_phase != RoundPhase::Rolling {
+      return vec![error(ErrorCode::WrongPhase)];
+    }
+    self.previous = None;
+    let roll = get_dice_value();
+    self.game.dice_throws.push(roll);
+    let can_roll_again = roll == 6 && self.game.dice_throws.len() < 3;
+    let mut messages = vec![ServerMessage::DiceValue(roll, can_roll_again)];
+    if can_roll_again {
+      return messages;
+    }
+
+    if self.game.dice_throws.iter().sum::<usize>() == 18 || !self.game.has_legal_moves() {
+      messages.extend(self.skip_player());
+      return messages;
+    }
+    self.game.round_phase = RoundPhase::Moving;
+    messages.push(self.legal_moves());
+    messages
+  }
+
+  fn play(&mut self, move_type: MoveType) -> Vec<ServerMessage> {
+    if self.game.round_phase != RoundPhase::Moving {
+      return vec![error(ErrorCode::WrongPhase)];
+    }
+    let before = self.game.clone();
+    match play_round(&mut self.game, move_type) {
+      MoveResult::Error(reason) => vec![error(ErrorCode::IllegalMove { reason })],
+      MoveResult::Winner(_) => vec![self.game_update()],
+      MoveResult::Success(_) => {
+        self.previous = Some(before);
+        let mut messages = vec![self.game_update()];
+        // the player has some dice left
+        if self.game.round_phase == RoundPhase::Moving && !self.game.is_current_player_ai() {
+          messages.push(self.legal_moves());
+        }
+        messages
+      }
+    }
+  }
+
+  /// restores the game from before the last move, the dice stay the same
+  fn undo(&mut self) -> Vec<ServerMessage> {
+    let mut previous = match self.previous.take() {
+      Some(previous) => previous,
+      None => return vec![error(ErrorCode::NothingToUndo)],
+    };
+    // the sequence keeps growing, the board animates back to the restored position
+    previous.sequence = self.game.sequence;
+    self.game = previous;
+    vec![
+      self.game_update(),
+      ServerMessage::UndoResolved(true),
+      self.legal_moves(),
+    ]
+  }
+
+  fn skip_player(&mut self) -> Vec<ServerMessage> {
+    self.game.update_current_player();
+    self.game.dice_throws.clear();
+    vec![ServerMessage::SkipPlayer, self.game_update()]
+  }
+
+  fn legal_moves(&self) -> ServerMessage {
+    match self.game.ruleset.split_dice {
+      true => ServerMessage::LegalMovesPerDie(self.game.legal_moves_per_die()),
+      false => ServerMessage::LegalMoves(self.game.legal_moves(self.game.dice_throws.iter().sum())),
+    }
+  }
+
+  /// every update gets a new sequence number, the board animates between them
+  fn game_update(&mut self) -> ServerMessage {
+    self.game.sequence += 1;
+    ServerMessage::GameUpdate(self.game())
+  }
+}
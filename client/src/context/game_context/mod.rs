@@ -1,4 +1,5 @@
 pub mod context;
 pub mod game_reducer;
 pub mod hook;
+pub mod local_game;
 pub mod provider;
@@ -3,6 +3,7 @@ use yew::prelude::*;
 use super::{
   context::GameContext,
   hook::{use_game, UseGameProps},
+  local_game::LocalSetup,
 };
 
 #[derive(Properties, PartialEq, Clone)]
@@ -10,12 +11,18 @@ pub struct GameProviderProps {
   #[prop_or_default]
   pub children: Children,
   pub game_id: String,
+  #[prop_or_default]
+  pub spectator: bool,
+  #[prop_or_default]
+  pub local: Option<LocalSetup>,
 }
 
 #[function_component(GameProvider)]
 pub fn game_provider(props: &GameProviderProps) -> Html {
   let context = use_game(&UseGameProps {
     game_id: props.game_id.clone(),
+    spectator: props.spectator,
+    local: props.local.clone(),
   });
 
   html! {
@@ -1,3 +1,4 @@
+use wasm_bindgen_futures::spawn_local;
 use yew::{function_component, html};
 
 mod bindings;
@@ -10,6 +11,7 @@ mod types;
 mod utils;
 
 use routes::Routes;
+use utils::get_host::load_hosts;
 
 #[function_component(App)]
 pub fn app() -> Html {
@@ -17,5 +19,9 @@ pub fn app() -> Html {
 }
 
 fn main() {
-  yew::start_app::<App>();
+  // requests need the server's address, so it's known before anything renders
+  spawn_local(async {
+    load_hosts().await;
+    yew::start_app::<App>();
+  });
 }
@@ -1,17 +0,0 @@
-use core::fmt;
-
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Hash, Eq)]
-pub enum Color {
-  Red,
-  Green,
-  Blue,
-  Yellow,
-}
-
-impl fmt::Display for Color {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f, "{:?}", self)
-  }
-}
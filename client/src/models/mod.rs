@@ -1,5 +1,6 @@
-pub mod color;
 pub mod die_info;
-pub mod game;
-pub mod messages;
-pub mod player;
+
+pub use engine::models::{
+  chat, color, game, game_delta, game_move, game_summary, messages, piece, player, ruleset,
+  server_error, visibility,
+};
@@ -1,53 +1,171 @@
 use std::rc::Rc;
 
+use futures::SinkExt;
+use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
 
 use crate::components::board::Board;
+use crate::components::button::Button;
+use crate::components::chat_panel::ChatPanel;
+use crate::components::checkbox::Checkbox;
+use crate::components::dialogs::undo_dialog::UndoDialog;
 use crate::components::dialogs::winner_dialog::WinnerDialog;
+use crate::components::icon::Icon;
 use crate::components::player::{Player, PlayerButtonPosition};
 use crate::context::dialog::context::{DialogContext, OpenDialogOptions};
 use crate::context::game_context::context::GameContext;
 use crate::models::color::Color;
+use crate::models::messages::ClientMessage;
+use crate::utils::color_to_name::color_to_name;
 
 #[function_component(Game)]
 pub fn game() -> Html {
-  let DialogContext { open } = use_context::<DialogContext>().expect("context not found");
-  let GameContext { game, .. } = use_context::<GameContext>().expect("context not found");
+  let DialogContext { open, close } = use_context::<DialogContext>().expect("context not found");
+  let GameContext {
+    game,
+    spectator,
+    spectator_count,
+    player_color,
+    sender,
+    undo_request,
+    confirm_moves,
+    set_confirm_moves,
+    selected,
+    select,
+    play_piece,
+    ..
+  } = use_context::<GameContext>().expect("context not found");
+  let undo_prompt_open = use_mut_ref(|| false);
 
-  let winner_name = game
-    .winner
-    .as_ref()
-    .and_then(|color| game.players.iter().find(|player| player.color == *color))
-    .map(|player| player.name.clone());
+  let send = Callback::from(move |message: ClientMessage| {
+    let sender = sender.clone();
+    spawn_local(async move {
+      if let Some(mut sender) = sender {
+        sender.0.send(message).await.ok();
+      }
+    });
+  });
+
+  // the game is over once there is a winner, older games might not have placements recorded
+  let ranking = game.winner.as_ref().map(|winner| {
+    let placements = if game.placements.is_empty() {
+      vec![winner.clone()]
+    } else {
+      game.placements.clone()
+    };
+    placements
+      .into_iter()
+      .map(|color| (color.clone(), color_to_name(&game, color)))
+      .collect::<Vec<_>>()
+  });
+
+  // the other players are asked whether the move may be taken back
+  {
+    let open = open.clone();
+    let game = game.clone();
+    let send = send.clone();
+    let player_color = player_color.clone();
+    use_effect_with_deps(
+      move |undo_request| {
+        match undo_request {
+          Some(color) if !spectator && *color != player_color => {
+            let name = color_to_name(&game, color.clone());
+            let color = color.clone();
+            let answer =
+              Callback::from(move |accepted| send.emit(ClientMessage::AnswerUndo(accepted)));
+            *undo_prompt_open.borrow_mut() = true;
+            open.emit(OpenDialogOptions {
+              content: Rc::new(move |close| {
+                html! { <UndoDialog {close} color={color.clone()} name={name.clone()} answer={answer.clone()} /> }
+              }),
+            });
+          }
+          // somebody else refused or the game went on
+          None if undo_prompt_open.replace(false) => close.emit(()),
+          _ => {}
+        }
+        || {}
+      },
+      undo_request.clone(),
+    );
+  }
 
   use_effect_with_deps(
-    move |winner_name| {
-      if let Some(winner_name) = winner_name.clone() {
+    move |ranking| {
+      if let Some(ranking) = ranking.clone() {
         open.emit(OpenDialogOptions {
           content: Rc::new(
-            move |close| html! { <WinnerDialog {close} winner_name={winner_name.clone()} /> },
+            move |close| html! { <WinnerDialog {close} ranking={ranking.clone()} /> },
           ),
         });
       }
 
       || {}
     },
-    winner_name,
+    ranking,
   );
 
+  let spectators = html! {
+    <div class="flex justify-center items-center gap-2 text-lg font-semibold text-neutral-600">
+      <Icon class="fas fa-eye" />
+      {
+        if spectator {
+          format!("You are spectating ({} watching)", spectator_count)
+        } else {
+          format!("{} watching", spectator_count)
+        }
+      }
+    </div>
+  };
+
+  let request_undo = Callback::from(move |_| send.emit(ClientMessage::RequestUndo));
+  let undo = html! {
+    <Button class="w-full" bg_color="bg-neutral-600" onclick={request_undo} disabled={undo_request.is_some() || game.winner.is_some()} icon={html! { <Icon class="fas fa-undo" /> }}>
+      {"Take back my move"}
+    </Button>
+  };
+
+  // the selected pawn is moved with a second click on it or here
+  let confirmation = match selected {
+    Some(piece_id) => {
+      let confirm = Callback::from(move |_| play_piece.emit(piece_id));
+      let cancel = Callback::from(move |_| select.emit(None));
+      html! {
+        <div class="flex items-center gap-3">
+          <Button class="w-full" onclick={confirm} icon={html! { <Icon class="fas fa-check" /> }}>{"Confirm move"}</Button>
+          <Button class="w-full" bg_color="bg-red-700" onclick={cancel} icon={html! { <Icon class="fas fa-times" /> }}>{"Cancel"}</Button>
+        </div>
+      }
+    }
+    None => html! {},
+  };
+
+  let controls = html! {
+    <div class="flex flex-col gap-3">
+      { confirmation }
+      { undo }
+      <Checkbox label={"Confirm moves before sending them".to_string()} checked={confirm_moves} onchange={set_confirm_moves} />
+    </div>
+  };
+
   html! {
-    <div class="py-4 flex">
-      <div class="flex flex-col justify-between item-center p-4 max-w-md flex-grow">
-        <Player position={PlayerButtonPosition::Bottom} color={Color::Yellow} />
-        <Player position={PlayerButtonPosition::Top} color={Color::Green} />
+    <>
+      { if spectator || spectator_count > 0 { spectators } else { html! {} } }
+      <div class="py-4 flex">
+        <div class="flex flex-col justify-between item-center p-4 max-w-md flex-grow">
+          <Player position={PlayerButtonPosition::Bottom} color={Color::Yellow} />
+          <ChatPanel />
+          { if spectator { html! {} } else { controls } }
+          <Player position={PlayerButtonPosition::Top} color={Color::Green} />
+        </div>
+        <div class="flex-grow">
+          <Board />
+        </div>
+        <div class="flex flex-col justify-between item-center p-4 max-w-md flex-grow">
+          <Player position={PlayerButtonPosition::Bottom} color={Color::Blue} />
+          <Player position={PlayerButtonPosition::Top} color={Color::Red} />
+        </div>
       </div>
-      <div class="flex-grow">
-        <Board />
-      </div>
-      <div class="flex flex-col justify-between item-center p-4 max-w-md flex-grow">
-        <Player position={PlayerButtonPosition::Bottom} color={Color::Blue} />
-        <Player position={PlayerButtonPosition::Top} color={Color::Red} />
-      </div>
-    </div>
+    </>
   }
 }
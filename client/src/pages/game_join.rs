@@ -14,21 +14,25 @@ use crate::components::content::Content;
 use crate::components::text_input::TextInput;
 use crate::context::toasts::context::{ToastOptions, ToastVariant, ToastsContext};
 use crate::routes::GameRoute;
-use crate::utils::get_host::HTTP_STRING;
+use crate::utils::get_host::http_string;
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct GameJoinProps {
   pub id: String,
+  /// needed to join a private game
+  #[prop_or_default]
+  pub invite_code: Option<String>,
 }
 
 #[derive(serde::Serialize, Deserialize)]
 pub struct JoinGameBody {
   pub name: String,
+  pub invite_code: Option<String>,
 }
 
 #[function_component(GameJoin)]
 pub fn game_join(props: &GameJoinProps) -> Html {
-  let GameJoinProps { id } = props.clone();
+  let GameJoinProps { id, invite_code } = props.clone();
   let ToastsContext { open } = use_context().expect("context not found");
   let history = use_history().unwrap();
   let nickname = use_state::<String, _>(|| "".into());
@@ -55,13 +59,15 @@ pub fn game_join(props: &GameJoinProps) -> Html {
       let open = open.clone();
       let nickname = nickname.clone();
       let id = id.clone();
+      let invite_code = invite_code.clone();
       let history = history.clone();
       spawn_local(async move {
         let body = JoinGameBody {
           name: (*nickname).clone(),
+          invite_code,
         };
         let body_json = serde_json::to_string(&body).unwrap();
-        let res = Request::put(format!("{}/games/{}",HTTP_STRING, id).as_str())
+        let res = Request::put(format!("{}/games/{}",http_string(), id).as_str())
           .header("Content-Type", "application/json")
           .body(body_json)
           .send()
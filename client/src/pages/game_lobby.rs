@@ -14,7 +14,7 @@ use crate::components::outlined_item::OutlinedItem;
 use crate::context::game_context::context::GameContext;
 use crate::models::messages::{ClientMessage, ServerMessage};
 use crate::routes::{GameRoute, MainRoute};
-use crate::utils::get_host::JOIN_STRING;
+use crate::utils::get_host::join_string;
 
 #[derive(Properties, PartialEq, Clone)]
 pub struct GameLobbyProps {
@@ -25,7 +25,10 @@ pub struct GameLobbyProps {
 pub fn game_lobby(props: &GameLobbyProps) -> Html {
   let GameLobbyProps { id } = props.clone();
   let GameContext {
-    subscribe, sender, ..
+    subscribe,
+    sender,
+    game,
+    ..
   } = use_context::<GameContext>().expect("provider is not a parent");
   let history = use_history().unwrap();
   let player_count = use_state(|| 0);
@@ -87,6 +90,20 @@ pub fn game_lobby(props: &GameLobbyProps) -> Html {
     <Icon class="fas fa-sign-out-alt"/>
   };
 
+  // private games are joined through the invite code instead of the game id
+  let invite = match game.invite_code.clone() {
+    Some(code) => html! {
+      <>
+        <CopyBar content={ format!("{}/join/{}", join_string(), code) } />
+        <p class="text-lg text-neutral-600 font-semibold mt-3">{"Or let them type in the invite code"}</p>
+        <CopyBar content={ code } />
+      </>
+    },
+    None => html! {
+      <CopyBar content={ format!("{}/games/{}/join",join_string(), id) } />
+    },
+  };
+
   let players_item = html! {
     {format!("{} / 4", *player_count)}
   };
@@ -106,7 +123,7 @@ pub fn game_lobby(props: &GameLobbyProps) -> Html {
       </div>
       <Card class="w-full px-8 py-14 lg:px-40">
         <p class="text-xl text-neutral-600 font-bold">{"Share the link with your friends and start the game"}</p>
-        <CopyBar content={ format!("{}/games/{}/join",JOIN_STRING, id) } />
+        { invite }
         <div class="flex items-center gap-3 text-neutral-600 mt-16">
           <Icon class="fas fa-info-circle" />
           <p class="text-xl font-bold">{"Starting the game without all 4 players will fill the remaining spots with
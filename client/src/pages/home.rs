@@ -1,57 +1,161 @@
+use engine::models::position::Position;
 use reqwasm::http::Request;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
 use crate::components::button::Button;
 use crate::components::card::Card;
+use crate::components::checkbox::Checkbox;
 use crate::components::content::Content;
+use crate::components::game_browser::GameBrowser;
 use crate::components::icon::Icon;
+use crate::components::text_input::TextInput;
 
 use crate::context::toasts::context::{ToastOptions, ToastVariant, ToastsContext};
+use crate::models::game_summary::GameStatus;
+use crate::models::ruleset::Ruleset;
+use crate::models::visibility::Visibility;
 use crate::routes::MainRoute;
-use crate::utils::get_host::HTTP_STRING;
+use crate::utils::get_host::http_string;
+
+#[derive(Serialize)]
+pub struct CreateGameBody {
+  pub ruleset: Ruleset,
+  pub visibility: Visibility,
+  pub position: Option<Position>,
+}
+
+#[derive(Deserialize)]
+pub struct CreateGameResponse {
+  pub id: String,
+  pub invite_code: Option<String>,
+}
 
 #[function_component(Home)]
 pub fn home() -> Html {
   let history = use_history().unwrap();
   let ToastsContext { open } = use_context::<ToastsContext>().expect("context not found");
+  let full_ranking = use_state(|| false);
+  let split_dice = use_state(|| false);
+  let private = use_state(|| false);
+  let invite_code = use_state::<String, _>(|| "".into());
+
+  let on_full_ranking_change = {
+    let full_ranking = full_ranking.clone();
+    Callback::from(move |checked: bool| full_ranking.set(checked))
+  };
+
+  let on_split_dice_change = {
+    let split_dice = split_dice.clone();
+    Callback::from(move |checked: bool| split_dice.set(checked))
+  };
+
+  let on_private_change = {
+    let private = private.clone();
+    Callback::from(move |checked: bool| private.set(checked))
+  };
+
+  let on_invite_code_change = {
+    let invite_code = invite_code.clone();
+    Callback::from(move |event: InputEvent| {
+      let target = event.target();
+      let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
 
-  let onclick = Callback::from(move |_| {
+      if let Some(element) = input {
+        invite_code.set(element.value());
+      }
+    })
+  };
+
+  let on_invite_join = {
     let history = history.clone();
-    let open = open.clone();
-    spawn_local(async move {
-      let res = Request::post(format!("{}/games",HTTP_STRING).as_str()).send().await;
-
-      let resp = match res {
-        Ok(resp) => resp,
-        Err(_) => {
-          open.emit(ToastOptions {
-            message: "Request to server failed".into(),
-            variant: ToastVariant::Error,
-          });
-          return;
-        }
-      };
+    let invite_code = invite_code.clone();
+    Callback::from(move |_| {
+      history.push(MainRoute::InviteJoin {
+        code: invite_code.trim().to_uppercase(),
+      })
+    })
+  };
 
-      let id = match resp.text().await {
-        Ok(id) => id,
-        Err(_) => {
-          open.emit(ToastOptions {
-            message: "Server failed creating new game".into(),
-            variant: ToastVariant::Error,
-          });
-          return;
-        }
+  let on_quick_match = {
+    let history = history.clone();
+    Callback::from(move |_| history.push(MainRoute::QuickMatch))
+  };
+
+  let on_local_game = {
+    let history = history.clone();
+    Callback::from(move |_| history.push(MainRoute::LocalGame))
+  };
+
+  let on_position_editor = {
+    let history = history.clone();
+    Callback::from(move |_| history.push(MainRoute::PositionEditor))
+  };
+
+  let onclick = {
+    let full_ranking = full_ranking.clone();
+    let split_dice = split_dice.clone();
+    let private = private.clone();
+    Callback::from(move |_| {
+      let history = history.clone();
+      let open = open.clone();
+      let body = CreateGameBody {
+        ruleset: Ruleset {
+          full_ranking: *full_ranking,
+          split_dice: *split_dice,
+        },
+        visibility: if *private {
+          Visibility::Private
+        } else {
+          Visibility::Public
+        },
+        position: None,
       };
+      spawn_local(async move {
+        let body_json = serde_json::to_string(&body).unwrap();
+        let res = Request::post(format!("{}/games", http_string()).as_str())
+          .header("Content-Type", "application/json")
+          .body(body_json)
+          .send()
+          .await;
+
+        let resp = match res {
+          Ok(resp) => resp,
+          Err(_) => {
+            open.emit(ToastOptions {
+              message: "Request to server failed".into(),
+              variant: ToastVariant::Error,
+            });
+            return;
+          }
+        };
 
-      open.emit(ToastOptions {
-        message: "Game successfully created!".into(),
-        variant: ToastVariant::Success,
+        let created = match resp.json::<CreateGameResponse>().await {
+          Ok(created) => created,
+          Err(_) => {
+            open.emit(ToastOptions {
+              message: "Server failed creating new game".into(),
+              variant: ToastVariant::Error,
+            });
+            return;
+          }
+        };
+
+        open.emit(ToastOptions {
+          message: "Game successfully created!".into(),
+          variant: ToastVariant::Success,
+        });
+        match created.invite_code {
+          Some(code) => history.push(MainRoute::InviteJoin { code }),
+          None => history.push(MainRoute::GameJoin { id: created.id }),
+        }
       });
-      history.push(MainRoute::GameJoin { id })
-    });
-  });
+    })
+  };
 
   let create_icon = html! {
     <Icon class={classes!(String::from("fas fa-gamepad"))}/>
@@ -74,7 +178,28 @@ pub fn home() -> Html {
           <li class="text-lg font-semibold text-neutral-600">{"Invite up to 4 friends to play"}</li>
           <li class="text-lg font-semibold text-neutral-600">{"Start the game when ready!"}</li>
         </ol>
+        <div class="flex flex-col gap-2 mb-8">
+          <Checkbox label={"Keep playing until all places are decided".to_string()} checked={*full_ranking} onchange={on_full_ranking_change} />
+          <Checkbox label={"Use each die of a bonus throw for a separate move".to_string()} checked={*split_dice} onchange={on_split_dice_change} />
+          <Checkbox label={"Private lobby - joinable only with an invite code".to_string()} checked={*private} onchange={on_private_change} />
+        </div>
         <Button class="w-full" {onclick} icon={create_icon}>{"Create new game lobby"}</Button>
+        <Button class="w-full mt-3" bg_color="bg-green-600" onclick={on_quick_match} icon={html! { <Icon class="fas fa-bolt" /> }}>{"Play now"}</Button>
+        <Button class="w-full mt-3" bg_color="bg-neutral-600" onclick={on_local_game} icon={html! { <Icon class="fas fa-desktop" /> }}>{"Play locally"}</Button>
+        <Button class="w-full mt-3" bg_color="bg-neutral-600" onclick={on_position_editor} icon={html! { <Icon class="fas fa-chess-board" /> }}>{"Set up a position"}</Button>
+      </Card>
+      <Card class="w-full mt-6 px-8 py-10 lg:px-40">
+        <p class="text-2xl font-bold text-neutral-800 mb-6">{ "Got an invite code?" }</p>
+        <div class="flex items-end gap-3">
+          <TextInput value={(*invite_code).clone()} label={"Invite code (e.g. LUDO-7K3P):".to_string()} onchange={on_invite_code_change} />
+          <Button onclick={on_invite_join} disabled={invite_code.trim().is_empty()} icon={html! { <Icon class="fas fa-key" /> }}>{"Join"}</Button>
+        </div>
+      </Card>
+      <Card class="w-full mt-6 px-8 py-10 lg:px-40">
+        <GameBrowser />
+      </Card>
+      <Card class="w-full mt-6 px-8 py-10 lg:px-40">
+        <GameBrowser status={GameStatus::InProgress} />
       </Card>
     </Content>
   }
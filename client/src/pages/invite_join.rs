@@ -0,0 +1,66 @@
+use reqwasm::http::Request;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::components::content::Content;
+use crate::components::spinner::Spinner;
+use crate::context::toasts::context::{ToastOptions, ToastVariant, ToastsContext};
+use crate::pages::game_join::GameJoin;
+use crate::routes::MainRoute;
+use crate::utils::get_host::http_string;
+
+#[derive(Properties, PartialEq, Clone)]
+pub struct InviteJoinProps {
+  pub code: String,
+}
+
+/// resolves the invite code to a game and lets the player join it
+#[function_component(InviteJoin)]
+pub fn invite_join(props: &InviteJoinProps) -> Html {
+  let InviteJoinProps { code } = props.clone();
+  let ToastsContext { open } = use_context::<ToastsContext>().expect("context not found");
+  let history = use_history().unwrap();
+  let game_id = use_state::<Option<String>, _>(|| None);
+
+  {
+    let game_id = game_id.clone();
+    use_effect_with_deps(
+      move |code| {
+        let code = code.clone();
+        spawn_local(async move {
+          let res = Request::get(format!("{}/games/invite/{}", http_string(), code).as_str())
+            .send()
+            .await;
+
+          match res {
+            Ok(resp) if resp.ok() => {
+              if let Ok(id) = resp.text().await {
+                game_id.set(Some(id));
+              }
+            }
+            _ => {
+              open.emit(ToastOptions {
+                message: format!("Invite code {} is not valid", code),
+                variant: ToastVariant::Error,
+              });
+              history.push(MainRoute::Home);
+            }
+          }
+        });
+
+        || {}
+      },
+      code.clone(),
+    );
+  }
+
+  match (*game_id).clone() {
+    Some(id) => html! { <GameJoin {id} invite_code={Some(code)} /> },
+    None => html! {
+      <Content class="h-full grid place-items-center">
+        <Spinner />
+      </Content>
+    },
+  }
+}
@@ -0,0 +1,122 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::components::button::Button;
+use crate::components::card::Card;
+use crate::components::checkbox::Checkbox;
+use crate::components::content::Content;
+use crate::components::icon::Icon;
+use crate::components::outlined_item::OutlinedItem;
+use crate::context::game_context::local_game::LocalSetup;
+use crate::context::game_context::provider::GameProvider;
+use crate::models::color::Color;
+use crate::models::ruleset::Ruleset;
+use crate::pages::game::Game;
+use crate::routes::MainRoute;
+
+/// sets up a game played on this screen, every color is played by a human or a bot
+#[function_component(LocalGame)]
+pub fn local_game() -> Html {
+  let history = use_history().unwrap();
+  let humans = use_state(|| vec![Color::Green]);
+  let full_ranking = use_state(|| false);
+  let split_dice = use_state(|| false);
+  let setup = use_state::<Option<LocalSetup>, _>(|| None);
+
+  if let Some(setup) = (*setup).clone() {
+    return html! {
+      <GameProvider game_id="local" local={Some(setup)}>
+        <Game />
+      </GameProvider>
+    };
+  }
+
+  let on_full_ranking_change = {
+    let full_ranking = full_ranking.clone();
+    Callback::from(move |checked: bool| full_ranking.set(checked))
+  };
+
+  let on_split_dice_change = {
+    let split_dice = split_dice.clone();
+    Callback::from(move |checked: bool| split_dice.set(checked))
+  };
+
+  let on_start = {
+    let humans = humans.clone();
+    let full_ranking = full_ranking.clone();
+    let split_dice = split_dice.clone();
+    Callback::from(move |_| {
+      setup.set(Some(LocalSetup {
+        humans: (*humans).clone(),
+        ruleset: Ruleset {
+          full_ranking: *full_ranking,
+          split_dice: *split_dice,
+        },
+        position: None,
+      }))
+    })
+  };
+
+  let redirect_to_home = Callback::from(move |_| history.push(MainRoute::Home));
+
+  let seat = |color: Color| {
+    let is_human = humans.contains(&color);
+    let onclick = {
+      let humans = humans.clone();
+      let color = color.clone();
+      Callback::from(move |_| {
+        let mut next = (*humans).clone();
+        match next.iter().position(|human| *human == color) {
+          Some(index) => {
+            next.remove(index);
+          }
+          None => next.push(color.clone()),
+        }
+        humans.set(next);
+      })
+    };
+    let (label, bg_color, icon) = if is_human {
+      ("Human", "bg-primary-600", "fas fa-user")
+    } else {
+      ("Bot", "bg-neutral-400", "fas fa-robot")
+    };
+    html! {
+      <OutlinedItem label={format!("{:?}", color)} item={html! {
+        <Button bg_color={bg_color.to_string()} {onclick} icon={html! { <Icon class={classes!(String::from(icon))} /> }}>
+          { label }
+        </Button>
+      }} />
+    }
+  };
+  let seats = vec![Color::Green, Color::Yellow, Color::Blue, Color::Red]
+    .into_iter()
+    .map(seat);
+
+  html! {
+    <Content class="py-12 h-full">
+      <div class="flex items-center mb-6 w-full">
+        <div class="flex flex-col gap-2 w-full justify-between">
+          <p class="text-5xl font-bold">{"Ludo"}</p>
+          <p class="text-2xl text-neutral-600 font-bold">{"Play on this screen, no connection needed"}</p>
+        </div>
+        <img class="h-28" src="/assets/ludo.svg" alt="" />
+      </div>
+      <Card class="w-full px-8 py-14 lg:px-40">
+        <p class="text-2xl font-bold text-neutral-800 mb-6">{ "Who is playing?" }</p>
+        <div class="flex flex-col gap-3 mb-8">
+          { for seats }
+        </div>
+        <div class="flex flex-col gap-2 mb-8">
+          <Checkbox label={"Keep playing until all places are decided".to_string()} checked={*full_ranking} onchange={on_full_ranking_change} />
+          <Checkbox label={"Use each die of a bonus throw for a separate move".to_string()} checked={*split_dice} onchange={on_split_dice_change} />
+        </div>
+        <div class="flex items-center gap-3">
+          <Button class="w-full" onclick={on_start} icon={html! { <Icon class="fas fa-play" /> }}>
+            { if humans.is_empty() { "Watch the bots" } else { "Start" } }
+          </Button>
+          <Button class="w-full" bg_color="bg-red-700" onclick={redirect_to_home} icon={html! { <Icon class="fas fa-sign-out-alt" /> }}>{"Back"}</Button>
+        </div>
+      </Card>
+    </Content>
+  }
+}
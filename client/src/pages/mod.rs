@@ -2,4 +2,8 @@ pub mod game;
 pub mod game_join;
 pub mod game_lobby;
 pub mod home;
+pub mod invite_join;
+pub mod local_game;
 pub mod not_found;
+pub mod position_editor;
+pub mod quick_match;
@@ -0,0 +1,250 @@
+use engine::models::position::{ColorPosition, Position};
+use reqwasm::http::Request;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::components::button::Button;
+use crate::components::card::Card;
+use crate::components::checkbox::Checkbox;
+use crate::components::content::Content;
+use crate::components::icon::Icon;
+use crate::components::text_input::TextInput;
+use crate::context::game_context::local_game::LocalSetup;
+use crate::context::game_context::provider::GameProvider;
+use crate::context::toasts::context::{ToastOptions, ToastVariant, ToastsContext};
+use crate::models::color::Color;
+use crate::models::ruleset::Ruleset;
+use crate::models::visibility::Visibility;
+use crate::pages::game::Game;
+use crate::pages::home::{CreateGameBody, CreateGameResponse};
+use crate::routes::MainRoute;
+use crate::utils::get_host::http_string;
+
+/// pieces of one color as typed in, the fields are comma separated
+#[derive(Clone, Default, PartialEq)]
+struct ColorInputs {
+  board: String,
+  home: String,
+  finished: String,
+}
+
+fn parse_fields(text: &str) -> Result<Vec<usize>, String> {
+  text
+    .split(',')
+    .map(|field| field.trim())
+    .filter(|field| !field.is_empty())
+    .map(|field| {
+      field
+        .parse()
+        .map_err(|_| format!("{} isn't a field number.", field))
+    })
+    .collect()
+}
+
+fn parse_color_position(inputs: &ColorInputs) -> Result<ColorPosition, String> {
+  let finished = match inputs.finished.trim() {
+    "" => 0,
+    count => count
+      .parse()
+      .map_err(|_| format!("{} isn't a number of pieces.", count))?,
+  };
+  Ok(ColorPosition {
+    board: parse_fields(&inputs.board)?,
+    home: parse_fields(&inputs.home)?,
+    finished,
+  })
+}
+
+fn build_position(
+  current_player: Color,
+  inputs: &[ColorInputs],
+  ruleset: &Ruleset,
+) -> Result<Position, String> {
+  let mut position = Position {
+    current_player,
+    ..Position::default()
+  };
+  for (color, inputs) in Color::ordered().into_iter().zip(inputs) {
+    *position.get_mut(color) = parse_color_position(inputs)?;
+  }
+  position
+    .validate(ruleset)
+    .map_err(|reason| reason.to_string())?;
+  Ok(position)
+}
+
+/// sets up a board state to start a game from, e.g. a puzzle or a reproduced bug report
+#[function_component(PositionEditor)]
+pub fn position_editor() -> Html {
+  let history = use_history().unwrap();
+  let ToastsContext { open } = use_context::<ToastsContext>().expect("context not found");
+  let inputs = use_state(|| vec![ColorInputs::default(); Color::ordered().len()]);
+  let current_player = use_state(|| Color::Green);
+  let full_ranking = use_state(|| false);
+  let split_dice = use_state(|| false);
+  let local_setup = use_state::<Option<LocalSetup>, _>(|| None);
+
+  if let Some(setup) = (*local_setup).clone() {
+    return html! {
+      <GameProvider game_id="local" local={Some(setup)}>
+        <Game />
+      </GameProvider>
+    };
+  }
+
+  let ruleset = Ruleset {
+    full_ranking: *full_ranking,
+    split_dice: *split_dice,
+  };
+  let position = build_position(*current_player, &inputs, &ruleset);
+
+  let on_full_ranking_change = {
+    let full_ranking = full_ranking.clone();
+    Callback::from(move |checked: bool| full_ranking.set(checked))
+  };
+
+  let on_split_dice_change = {
+    let split_dice = split_dice.clone();
+    Callback::from(move |checked: bool| split_dice.set(checked))
+  };
+
+  let on_play_locally = {
+    let position = position.clone();
+    let ruleset = ruleset.clone();
+    Callback::from(move |_| {
+      if let Ok(position) = &position {
+        local_setup.set(Some(LocalSetup {
+          humans: vec![Color::Green, Color::Yellow, Color::Blue, Color::Red],
+          ruleset: ruleset.clone(),
+          position: Some(position.clone()),
+        }))
+      }
+    })
+  };
+
+  let on_create = {
+    let history = history.clone();
+    let position = position.clone();
+    let ruleset = ruleset.clone();
+    Callback::from(move |_| {
+      let position = match &position {
+        Ok(position) => position.clone(),
+        Err(_) => return,
+      };
+      let history = history.clone();
+      let open = open.clone();
+      let body = CreateGameBody {
+        ruleset: ruleset.clone(),
+        visibility: Visibility::Public,
+        position: Some(position),
+      };
+      spawn_local(async move {
+        let body_json = serde_json::to_string(&body).unwrap();
+        let res = Request::post(format!("{}/games", http_string()).as_str())
+          .header("Content-Type", "application/json")
+          .body(body_json)
+          .send()
+          .await;
+
+        let created = match res {
+          Ok(resp) => resp.json::<CreateGameResponse>().await.ok(),
+          Err(_) => None,
+        };
+        match created {
+          Some(created) => history.push(MainRoute::GameJoin { id: created.id }),
+          None => open.emit(ToastOptions {
+            message: "Server failed creating new game".into(),
+            variant: ToastVariant::Error,
+          }),
+        }
+      });
+    })
+  };
+
+  let redirect_to_home = Callback::from(move |_| history.push(MainRoute::Home));
+
+  let on_input = |index: usize, update: fn(&mut ColorInputs, String)| {
+    let inputs = inputs.clone();
+    Callback::from(move |event: InputEvent| {
+      let target = event.target();
+      let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+      if let Some(element) = input {
+        let mut next = (*inputs).clone();
+        update(&mut next[index], element.value());
+        inputs.set(next);
+      }
+    })
+  };
+
+  let color_rows = Color::ordered()
+    .into_iter()
+    .enumerate()
+    .map(|(index, color)| {
+      let on_turn = *current_player == color;
+      let select = {
+        let current_player = current_player.clone();
+        Callback::from(move |_| current_player.set(color))
+      };
+      let (bg_color, label) = match on_turn {
+        true => ("bg-primary-600", "On turn"),
+        false => ("bg-neutral-400", "Waiting"),
+      };
+      html! {
+        <div class="flex flex-col gap-2">
+          <div class="flex items-center justify-between">
+            <p class="text-xl font-bold text-neutral-800">{ format!("{:?}", color) }</p>
+            <Button bg_color={bg_color.to_string()} onclick={select} icon={html! { <Icon class="fas fa-play" /> }}>
+              { label }
+            </Button>
+          </div>
+          <div class="flex gap-3">
+            <TextInput label={"Board fields:".to_string()} value={inputs[index].board.clone()} onchange={on_input(index, |inputs, value| inputs.board = value)} />
+            <TextInput label={"Home fields:".to_string()} value={inputs[index].home.clone()} onchange={on_input(index, |inputs, value| inputs.home = value)} />
+            <TextInput label={"Finished pieces:".to_string()} value={inputs[index].finished.clone()} onchange={on_input(index, |inputs, value| inputs.finished = value)} />
+          </div>
+        </div>
+      }
+    });
+
+  html! {
+    <Content class="py-12 h-full">
+      <div class="flex items-center mb-6 w-full">
+        <div class="flex flex-col gap-2 w-full justify-between">
+          <p class="text-5xl font-bold">{"Ludo"}</p>
+          <p class="text-2xl text-neutral-600 font-bold">{"Start a game from any position"}</p>
+        </div>
+        <img class="h-28" src="/assets/ludo.svg" alt="" />
+      </div>
+      <Card class="w-full px-8 py-14 lg:px-40">
+        <p class="text-2xl font-bold text-neutral-800 mb-2">{ "Where are the pieces?" }</p>
+        <p class="font-semibold text-neutral-600 mb-6">
+          { "Board fields are numbered 0 to 51 clockwise, pieces enter the board at 47 (green), 8 (yellow), 21 (blue) and 34 (red). \
+            Home fields are numbered 0 to 4, the pieces which aren't listed stay at start." }
+        </p>
+        <div class="flex flex-col gap-6 mb-8">
+          { for color_rows }
+        </div>
+        <div class="flex flex-col gap-2 mb-8">
+          <Checkbox label={"Keep playing until all places are decided".to_string()} checked={*full_ranking} onchange={on_full_ranking_change} />
+          <Checkbox label={"Use each die of a bonus throw for a separate move".to_string()} checked={*split_dice} onchange={on_split_dice_change} />
+        </div>
+        {
+          if let Err(message) = &position {
+            html! { <p class="font-semibold text-red-700 mb-4">{ message }</p> }
+          } else {
+            html! {}
+          }
+        }
+        <div class="flex items-center gap-3">
+          <Button class="w-full" onclick={on_create} disabled={position.is_err()} icon={html! { <Icon class="fas fa-gamepad" /> }}>{"Create game lobby"}</Button>
+          <Button class="w-full" bg_color="bg-neutral-600" onclick={on_play_locally} disabled={position.is_err()} icon={html! { <Icon class="fas fa-desktop" /> }}>{"Play locally"}</Button>
+          <Button class="w-full" bg_color="bg-red-700" onclick={redirect_to_home} icon={html! { <Icon class="fas fa-sign-out-alt" /> }}>{"Back"}</Button>
+        </div>
+      </Card>
+    </Content>
+  }
+}
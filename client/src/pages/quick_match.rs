@@ -0,0 +1,208 @@
+use futures::channel::oneshot;
+use futures::future::{select, Either};
+use futures::{SinkExt, StreamExt};
+use gloo::storage::{SessionStorage, Storage};
+use reqwasm::websocket::futures::WebSocket;
+use reqwasm::websocket::Message;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::components::button::Button;
+use crate::components::card::Card;
+use crate::components::content::Content;
+use crate::components::icon::Icon;
+use crate::components::outlined_item::OutlinedItem;
+use crate::components::spinner::Spinner;
+use crate::components::text_input::TextInput;
+use crate::context::toasts::context::{ToastOptions, ToastVariant, ToastsContext};
+use crate::models::messages::{QueueRequest, ServerMessage};
+use crate::models::ruleset::Ruleset;
+use crate::routes::{GameRoute, MainRoute};
+use crate::utils::error_message::error_message;
+use crate::utils::get_host::ws_string;
+
+#[function_component(QuickMatch)]
+pub fn quick_match() -> Html {
+  let ToastsContext { open } = use_context::<ToastsContext>().expect("context not found");
+  let history = use_history().unwrap();
+  let nickname = use_state::<String, _>(|| "".into());
+  let player_count = use_state(|| 4_usize);
+  // number of players waiting in our group, `None` while not searching
+  let waiting = use_state::<Option<usize>, _>(|| None);
+  let cancel = use_mut_ref::<Option<oneshot::Sender<()>>, _>(|| None);
+
+  // leave the queue when the page is left
+  {
+    let cancel = cancel.clone();
+    use_effect_with_deps(
+      move |_| {
+        move || {
+          if let Some(cancel) = cancel.borrow_mut().take() {
+            cancel.send(()).ok();
+          }
+        }
+      },
+      (),
+    );
+  }
+
+  let onchange = {
+    let nickname = nickname.clone();
+    Callback::from(move |event: InputEvent| {
+      let target = event.target();
+      let input = target.and_then(|t| t.dyn_into::<HtmlInputElement>().ok());
+
+      if let Some(element) = input {
+        nickname.set(element.value());
+      }
+    })
+  };
+
+  let on_search = {
+    let nickname = nickname.clone();
+    let player_count = player_count.clone();
+    let waiting = waiting.clone();
+    let cancel = cancel.clone();
+    let history = history.clone();
+    Callback::from(move |_| {
+      let request = QueueRequest {
+        name: (*nickname).clone(),
+        player_count: *player_count,
+        ruleset: Ruleset::default(),
+      };
+      let (cancel_tx, mut cancel_rx) = oneshot::channel::<()>();
+      cancel.borrow_mut().replace(cancel_tx);
+      waiting.set(Some(1));
+
+      let open = open.clone();
+      let waiting = waiting.clone();
+      let history = history.clone();
+      spawn_local(async move {
+        let ws = match WebSocket::open(format!("{}/games/matchmaking", ws_string()).as_str()) {
+          Ok(ws) => ws,
+          Err(_) => {
+            open.emit(ToastOptions {
+              message: "Couldn't connect to the server".into(),
+              variant: ToastVariant::Error,
+            });
+            waiting.set(None);
+            return;
+          }
+        };
+        let (mut write, mut read) = ws.split();
+        let json = serde_json::to_string(&request).unwrap();
+        write.send(Message::Text(json)).await.ok();
+
+        loop {
+          let text = match select(read.next(), &mut cancel_rx).await {
+            Either::Left((Some(Ok(Message::Text(text))), _)) => text,
+            _ => break,
+          };
+
+          match serde_json::from_str::<ServerMessage>(text.as_str()) {
+            Ok(ServerMessage::QueueUpdate(count)) => waiting.set(Some(count)),
+            Ok(ServerMessage::MatchFound { game_id, player_id }) => {
+              if SessionStorage::set("player_id", player_id).is_err() {
+                open.emit(ToastOptions {
+                  message: "Failed to set your player id".into(),
+                  variant: ToastVariant::Error,
+                });
+                break;
+              }
+              open.emit(ToastOptions {
+                message: "Match found, good luck!".into(),
+                variant: ToastVariant::Success,
+              });
+              history.push(GameRoute::Game { id: game_id });
+              break;
+            }
+            Ok(ServerMessage::Error(error)) => {
+              open.emit(ToastOptions {
+                message: error_message(&error.code),
+                variant: ToastVariant::Error,
+              });
+              waiting.set(None);
+              break;
+            }
+            _ => {}
+          }
+        }
+
+        write.close().await.ok();
+      });
+    })
+  };
+
+  let on_cancel = {
+    let waiting = waiting.clone();
+    let cancel = cancel.clone();
+    Callback::from(move |_| {
+      if let Some(cancel) = cancel.borrow_mut().take() {
+        cancel.send(()).ok();
+      }
+      waiting.set(None);
+    })
+  };
+
+  let redirect_to_home = Callback::from(move |_| {
+    history.push(MainRoute::Home);
+  });
+
+  let count_buttons = (2..=4).map(|count| {
+    let player_count = player_count.clone();
+    let bg_color = if *player_count == count {
+      "bg-primary-600"
+    } else {
+      "bg-neutral-400"
+    };
+    let onclick = Callback::from(move |_| player_count.set(count));
+    html! {
+      <Button class="w-full" bg_color={bg_color.to_string()} {onclick} disabled={waiting.is_some()}>
+        { format!("{} players", count) }
+      </Button>
+    }
+  });
+
+  let content = match *waiting {
+    Some(count) => html! {
+      <div class="flex flex-col gap-6 items-center">
+        <Spinner />
+        <OutlinedItem label="Players waiting" item={html! { {format!("{} / {}", count, *player_count)} }} />
+        <p class="text-lg font-semibold text-neutral-600">{"If nobody shows up in a while, the empty seats are taken by bots"}</p>
+        <Button class="w-full" bg_color="bg-red-700" onclick={on_cancel} icon={html! { <Icon class="fas fa-times" /> }}>{"Cancel"}</Button>
+      </div>
+    },
+    None => html! {
+      <>
+        <TextInput value={(*nickname).clone()} label={"Nickname:".to_string()} {onchange} />
+        <p class="font-semibold text-neutral-600 mt-6">{"Human players:"}</p>
+        <div class="flex items-center gap-3">
+          { for count_buttons }
+        </div>
+        <div class="flex items-center gap-3 mt-8">
+          <Button class="w-full" onclick={on_search} disabled={nickname.is_empty()} icon={html! { <Icon class="fas fa-bolt" /> }}>{"Find a match"}</Button>
+          <Button class="w-full" bg_color="bg-red-700" onclick={redirect_to_home} icon={html! { <Icon class="fas fa-sign-out-alt" /> }}>{"Back"}</Button>
+        </div>
+      </>
+    },
+  };
+
+  html! {
+    <Content class="py-12 h-full">
+      <div class="flex items-center mb-6 w-full">
+        <div class="flex flex-col gap-2 w-full justify-between">
+          <p class="text-5xl font-bold">{"Ludo"}</p>
+          <p class="text-2xl text-neutral-600 font-bold">{"Board game for up to 4 players online"}</p>
+        </div>
+        <img class="h-28" src="/assets/ludo.svg" alt="" />
+      </div>
+      <Card class="w-full px-8 py-14 lg:px-40">
+        <p class="text-2xl mb-8 text-neutral-600 font-semibold">{"Quick match"}</p>
+        { content }
+      </Card>
+    </Content>
+  }
+}
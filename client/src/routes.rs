@@ -8,7 +8,11 @@ use crate::pages::game::Game;
 use crate::pages::game_join::GameJoin;
 use crate::pages::game_lobby::GameLobby;
 use crate::pages::home::Home;
+use crate::pages::invite_join::InviteJoin;
+use crate::pages::local_game::LocalGame;
 use crate::pages::not_found::NotFound;
+use crate::pages::position_editor::PositionEditor;
+use crate::pages::quick_match::QuickMatch;
 
 #[derive(Clone, Routable, PartialEq)]
 pub enum MainRoute {
@@ -16,6 +20,16 @@ pub enum MainRoute {
   Home,
   #[at("/games/:id/join")]
   GameJoin { id: String },
+  #[at("/join/:code")]
+  InviteJoin { code: String },
+  #[at("/quick-match")]
+  QuickMatch,
+  #[at("/local")]
+  LocalGame,
+  #[at("/setup")]
+  PositionEditor,
+  #[at("/games/:id/watch")]
+  GameWatch { id: String },
   #[at("/games/:id/:rest")]
   GameSubroutes { id: String },
   #[not_found]
@@ -42,6 +56,15 @@ fn switch_main(routes: &MainRoute) -> Html {
   match routes {
     MainRoute::Home => html! {<Home />},
     MainRoute::GameJoin { id } => html! { <GameJoin id={ id.clone() } /> },
+    MainRoute::InviteJoin { code } => html! { <InviteJoin code={ code.clone() } /> },
+    MainRoute::QuickMatch => html! { <QuickMatch /> },
+    MainRoute::LocalGame => html! { <LocalGame /> },
+    MainRoute::PositionEditor => html! { <PositionEditor /> },
+    MainRoute::GameWatch { id } => html! {
+      <GameProvider game_id={ id.clone() } spectator=true>
+        <Game />
+      </GameProvider>
+    },
     MainRoute::GameSubroutes { id } => html! {
       <GameProvider game_id={ id.clone() }>
         <Switch<GameRoute> render={Switch::render(switch_game)} />
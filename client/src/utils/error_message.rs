@@ -0,0 +1,40 @@
+use engine::utils::enums::IllegalMoveReason;
+
+use crate::models::server_error::ErrorCode;
+
+/// text shown to the user for an error sent by the server
+pub fn error_message(code: &ErrorCode) -> String {
+  match code {
+    ErrorCode::NotYourTurn => "It is not your turn".into(),
+    ErrorCode::WrongPhase => "You can't do that right now".into(),
+    ErrorCode::IllegalMove { reason } => match reason {
+      IllegalMoveReason::OverjumpsHome => "This piece would overjump its home".into(),
+      IllegalMoveReason::HomeFieldOccupied => "That home field is already occupied".into(),
+      IllegalMoveReason::FieldOccupied => "That field is occupied by your own piece".into(),
+      IllegalMoveReason::CannotPromote => "You can't bring a new piece into play".into(),
+      IllegalMoveReason::NotYourPiece => "You can only move your own pieces".into(),
+      IllegalMoveReason::PieceFinished => "This piece has already finished".into(),
+      IllegalMoveReason::NoSuchDie => "That die can't be used for a move".into(),
+    },
+    ErrorCode::GameNotFound => "Game not found".into(),
+    ErrorCode::PlayerNotFound => "You are not a player of this game".into(),
+    ErrorCode::StorageFailure => "Something went wrong on the server, try again".into(),
+    ErrorCode::SpectatorNotAllowed => "Spectators can't play".into(),
+    ErrorCode::InvalidRequest => "Invalid request".into(),
+    ErrorCode::MessageEmpty => "Message is empty".into(),
+    ErrorCode::MessageTooLong { max_length } => {
+      format!("Messages can have at most {} characters", max_length)
+    }
+    ErrorCode::RateLimited => "You are sending messages too fast".into(),
+    // protocol errors mean the client itself is out of date or broken
+    ErrorCode::MalformedMessage | ErrorCode::UnknownCommand | ErrorCode::UnsupportedFrame => {
+      "The server didn't understand the request, try reloading the page".into()
+    }
+    ErrorCode::MessageTooLarge { .. } => "The request was too large".into(),
+    ErrorCode::NothingToUndo => "There is no move you could take back".into(),
+    ErrorCode::TooManySpectators { max_spectators } => {
+      format!("The game already has {} spectators", max_spectators)
+    }
+    ErrorCode::GameAlreadyStarted => "The game has already started".into(),
+  }
+}
@@ -1,17 +1,95 @@
-#[cfg(debug_assertions)]
-pub const HTTP_STRING: &str =  "http://127.0.0.1:8080";
+use reqwasm::http::Request;
+use serde::Deserialize;
+use std::cell::RefCell;
 
-#[cfg(not(debug_assertions))]
-pub const HTTP_STRING: &str = "http://3.78.187.83:8080";
+/// served next to the bundle, it points the client to a server on another origin
+const CONFIG_PATH: &str = "/config.json";
 
+/// `trunk serve` runs on its own port, the server listens on 8080 in development
 #[cfg(debug_assertions)]
-pub const JOIN_STRING: &str =  "http://localhost:3000";
+const DEFAULT_API_URL: Option<&str> = Some("http://127.0.0.1:8080");
 
 #[cfg(not(debug_assertions))]
-pub const JOIN_STRING: &str = "http://3.121.215.195";
+const DEFAULT_API_URL: Option<&str> = None;
 
-#[cfg(debug_assertions)]
-pub const WS_STRING: &str =   "ws://127.0.0.1:8080";
+/// contents of `config.json`, both urls are optional
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RuntimeConfig {
+  api_url: Option<String>,
+  ws_url: Option<String>,
+}
 
-#[cfg(not(debug_assertions))]
-pub const WS_STRING: &str = "ws://3.78.187.83:8080";
+#[derive(Clone)]
+struct Hosts {
+  http: String,
+  ws: String,
+  join: String,
+}
+
+thread_local! {
+  static HOSTS: RefCell<Option<Hosts>> = RefCell::new(None);
+}
+
+/// the websocket url is derived from the api url unless it's configured,
+/// pages served over https have to use wss
+fn resolve(config: RuntimeConfig, origin: &str, secure_page: bool) -> Hosts {
+  let http = config
+    .api_url
+    .or_else(|| DEFAULT_API_URL.map(String::from))
+    .unwrap_or_else(|| origin.to_string());
+  let http = http.trim_end_matches('/').to_string();
+  let ws = config
+    .ws_url
+    .unwrap_or_else(|| match http.split_once("://") {
+      Some(("https", rest)) => format!("wss://{}", rest),
+      Some((_, rest)) => format!("ws://{}", rest),
+      None => http.clone(),
+    });
+  let ws = match ws.strip_prefix("ws://") {
+    Some(rest) if secure_page => format!("wss://{}", rest),
+    _ => ws,
+  };
+  Hosts {
+    http,
+    ws: ws.trim_end_matches('/').to_string(),
+    join: origin.to_string(),
+  }
+}
+
+/// reads `config.json` before the app starts, the page origin is used without it
+pub async fn load_hosts() {
+  let config = match Request::get(CONFIG_PATH).send().await {
+    Ok(resp) if resp.ok() => resp.json::<RuntimeConfig>().await.unwrap_or_default(),
+    _ => RuntimeConfig::default(),
+  };
+  let location = web_sys::window().unwrap().location();
+  let origin = location.origin().unwrap_or_default();
+  let secure_page = location.protocol().ok().as_deref() == Some("https:");
+  let hosts = resolve(config, &origin, secure_page);
+  HOSTS.with(|cell| *cell.borrow_mut() = Some(hosts));
+}
+
+fn hosts() -> Hosts {
+  HOSTS.with(|cell| {
+    cell
+      .borrow()
+      .clone()
+      .expect("hosts are loaded before the app starts")
+  })
+}
+
+/// base url of the REST api
+pub fn http_string() -> String {
+  hosts().http
+}
+
+/// base url of the websockets
+pub fn ws_string() -> String {
+  hosts().ws
+}
+
+/// base url of the links shared with other players, that's where the client itself runs
+pub fn join_string() -> String {
+  hosts().join
+}
@@ -1,7 +1,10 @@
 mod clamp;
 pub mod color_to_name;
+pub mod error_message;
 mod resolve_color;
 pub mod get_host;
+pub mod settings;
+pub mod wire_format;
 pub use clamp::clamp;
 
 pub use resolve_color::resolve_bg_color_class;
@@ -0,0 +1,14 @@
+use gloo::storage::{LocalStorage, Storage};
+
+const CONFIRM_MOVES_KEY: &str = "confirm_moves";
+
+/// whether a click on a pawn only selects it and the move is sent after a confirmation,
+/// the setting is kept in the browser for all games
+pub fn confirm_moves_enabled() -> bool {
+  LocalStorage::get(CONFIRM_MOVES_KEY).unwrap_or(false)
+}
+
+pub fn set_confirm_moves(enabled: bool) {
+  // without storage the setting lasts until the page is reloaded
+  LocalStorage::set(CONFIRM_MOVES_KEY, enabled).ok();
+}
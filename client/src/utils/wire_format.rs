@@ -0,0 +1,25 @@
+use reqwasm::websocket::Message;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// JSON is readable in the devtools, MessagePack is a lot smaller
+#[cfg(debug_assertions)]
+pub const WIRE_FORMAT: &str = "json";
+
+#[cfg(not(debug_assertions))]
+pub const WIRE_FORMAT: &str = "msgpack";
+
+pub fn encode<T: Serialize>(value: &T) -> Message {
+  if WIRE_FORMAT == "msgpack" {
+    Message::Bytes(rmp_serde::to_vec_named(value).unwrap())
+  } else {
+    Message::Text(serde_json::to_string(value).unwrap())
+  }
+}
+
+/// the server may always answer with text, e.g. when it couldn't encode a message
+pub fn decode<T: DeserializeOwned>(message: &Message) -> Option<T> {
+  match message {
+    Message::Text(text) => serde_json::from_str(text).ok(),
+    Message::Bytes(bytes) => rmp_serde::from_slice(bytes).ok(),
+  }
+}
@@ -0,0 +1,5 @@
+//! rules of the game shared by the server and the client's local games
+
+pub mod models;
+pub mod types;
+pub mod utils;
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use super::color::Color;
+
+/// predefined reactions which can be sent with a single click
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EmoteKind {
+  ThumbsUp,
+  Laugh,
+  Surprised,
+  Angry,
+  Sad,
+  GoodGame,
+}
+
+impl EmoteKind {
+  pub fn all() -> [EmoteKind; 6] {
+    [
+      EmoteKind::ThumbsUp,
+      EmoteKind::Laugh,
+      EmoteKind::Surprised,
+      EmoteKind::Angry,
+      EmoteKind::Sad,
+      EmoteKind::GoodGame,
+    ]
+  }
+
+  pub fn symbol(&self) -> &'static str {
+    match self {
+      EmoteKind::ThumbsUp => "👍",
+      EmoteKind::Laugh => "😂",
+      EmoteKind::Surprised => "😮",
+      EmoteKind::Angry => "😠",
+      EmoteKind::Sad => "😢",
+      EmoteKind::GoodGame => "🤝",
+    }
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum ChatContent {
+  Text(String),
+  Emote(EmoteKind),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ChatMessage {
+  pub game_id: String,
+  pub name: String,
+  pub color: Color,
+  pub content: ChatContent,
+  pub sent_at: i64, // unix timestamp in milliseconds
+}
@@ -0,0 +1,1386 @@
+use crate::models::color::Color;
+use crate::types::Field;
+use crate::utils::enums::{IllegalMoveReason, MoveResult, RoundPhase};
+use serde::{Deserialize, Serialize};
+use std::iter;
+
+use super::game_delta::{GameChange, GameDelta};
+use super::game_move::{DieMoves, Move};
+use super::piece::{Piece, PieceLocation};
+use super::player::{Player, HOME_SIZE};
+use super::position::Position;
+use super::ruleset::Ruleset;
+use super::turn::{Turn, TurnMove};
+use super::visibility::Visibility;
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Game {
+  pub started: bool,
+  pub winner: Option<Color>,
+  /// finishing order, the first player is the winner
+  #[serde(default)]
+  pub placements: Vec<Color>,
+  #[serde(default)]
+  pub ruleset: Ruleset,
+  #[serde(default)]
+  pub visibility: Visibility,
+  /// only private games have an invite code
+  #[serde(default)]
+  pub invite_code: Option<String>,
+  pub players: Vec<Player>,
+  pub current_player: Color,
+  pub dice_throws: Vec<usize>,
+  pub round_phase: RoundPhase,
+  /// increased with every broadcast game state, see `GameDelta`
+  #[serde(default)]
+  pub sequence: u64,
+  /// move log of the finished turns, see `Game::to_notation`
+  #[serde(default)]
+  pub turns: Vec<Turn>,
+  /// turn of the current player, opened with the first move
+  #[serde(default)]
+  pub current_turn: Option<Turn>,
+  /// seed of the dice, kept only for the notation of imported games
+  #[serde(default)]
+  pub seed: Option<u64>,
+  /// position chosen when the game was created, it's set up once all seats are taken
+  #[serde(default)]
+  pub position: Option<Position>,
+}
+
+/// number of fields of the main board
+pub const BOARD_SIZE: usize = 52;
+
+impl Default for Game {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Game {
+  pub fn new() -> Self {
+    Game {
+      started: false,
+      winner: None,
+      placements: vec![],
+      ruleset: Ruleset::default(),
+      visibility: Visibility::default(),
+      invite_code: None,
+      players: vec![],
+      current_player: Color::ordered().first().unwrap().to_owned(),
+      round_phase: RoundPhase::Rolling,
+      dice_throws: vec![],
+      sequence: 0,
+      turns: vec![],
+      current_turn: None,
+      seed: None,
+      position: None,
+    }
+  }
+
+  /// there should be at most one winner at a time, therefore we take the first
+  /// player that meets the winning condition and hasn't been placed yet
+  pub fn check_winner(&self) -> Option<Color> {
+    for player in &self.players {
+      if player.check_winner() && !self.is_finished_player(player.color) {
+        return Some(player.color);
+      }
+    }
+    None
+  }
+
+  pub fn finish_game(&mut self, color: Color) {
+    self.winner = Some(color)
+  }
+
+  pub fn is_finished_player(&self, color: Color) -> bool {
+    self.placements.contains(&color)
+  }
+
+  /// appends a player who has just got all his pieces to finish to `placements`
+  /// returns the winner once the game is over - right after the first finished player,
+  /// or with `full_ranking` after all but one player have finished (the last one is appended too)
+  pub fn update_placements(&mut self) -> Option<Color> {
+    if let Some(color) = self.check_winner() {
+      self.placements.push(color);
+    }
+
+    if self.placements.is_empty() {
+      return None;
+    }
+
+    let remaining: Vec<Color> = self
+      .players
+      .iter()
+      .map(|player| player.color)
+      .filter(|color| !self.is_finished_player(*color))
+      .collect();
+
+    if !self.ruleset.full_ranking {
+      return self.placements.first().copied();
+    }
+
+    match remaining.len() {
+      0 | 1 => {
+        self.placements.extend(remaining);
+        self.placements.first().copied()
+      }
+      _ => None,
+    }
+  }
+
+  pub fn field_size(&self) -> usize {
+    BOARD_SIZE
+  }
+
+  pub fn pieces(&self) -> impl Iterator<Item = &Piece> {
+    self.players.iter().flat_map(|player| player.pieces.iter())
+  }
+
+  pub fn get_piece(&self, piece_id: usize) -> Option<&Piece> {
+    self.pieces().find(|piece| piece.id == piece_id)
+  }
+
+  /// piece standing on the main board at `position`
+  pub fn piece_on_board(&self, position: usize) -> Option<&Piece> {
+    let location = PieceLocation::Board(position % BOARD_SIZE);
+    self.pieces().find(|piece| piece.location == location)
+  }
+
+  fn get_field(&self, position: usize) -> Field {
+    let location = PieceLocation::Board(position % BOARD_SIZE);
+    self
+      .pieces()
+      .find(|piece| piece.location == location)
+      .map(|piece| piece.color)
+  }
+
+  fn set_piece_location(&mut self, piece_id: usize, location: PieceLocation) {
+    let piece = self
+      .get_current_player_mut()
+      .pieces
+      .iter_mut()
+      .find(|piece| piece.id == piece_id);
+    if let Some(piece) = piece {
+      piece.location = location;
+    }
+  }
+
+  pub fn get_player_id(&self, color: Color) -> String {
+    self.get_player(color).id.clone()
+  }
+
+  pub fn get_current_player_id(&self) -> String {
+    self.get_player_id(self.current_player)
+  }
+
+  /// moves the pieces of every seated player where `position` puts them,
+  /// the position should be validated with `Position::validate` first
+  pub fn set_up(&mut self, position: &Position) {
+    for player in &mut self.players {
+      let locations = position
+        .get(player.color)
+        .locations()
+        .into_iter()
+        .chain(iter::repeat(PieceLocation::Start));
+      for (piece, location) in player.pieces.iter_mut().zip(locations) {
+        piece.location = location;
+      }
+    }
+    self.placements = position.placements();
+    self.current_player = position.current_player;
+  }
+
+  /// passes the turn to the next player, players who have already finished are skipped
+  pub fn update_current_player(&mut self) {
+    let turn = self
+      .current_turn
+      .take()
+      .unwrap_or_else(|| Turn::new(self.current_player, self.dice_throws.clone()));
+    self.turns.push(turn);
+
+    for _ in 0..Color::ordered().len() {
+      self.current_player = match self.current_player {
+        Color::Yellow => Color::Blue,
+        Color::Blue => Color::Red,
+        Color::Red => Color::Green,
+        Color::Green => Color::Yellow,
+      };
+      if !self.is_finished_player(self.current_player) {
+        break;
+      }
+    }
+    self.round_phase = RoundPhase::Rolling;
+  }
+
+  /// returns how many steps we need to make to reach the first field of player's home
+  /// e.g. curr_pos = 0, end_pos = 39 => distance = 40 (need to throw 40 to get to home)
+  /// max(end_pos + field_size) = 39, max(curr_pos) = 39
+  pub fn distance_from_home(&self, current_position: usize) -> usize {
+    // position of the field right in front of home
+    let end_position = self.get_end_position();
+    // +1 to get to the first home field
+    (end_position + self.field_size() - current_position) % self.field_size() + 1
+  }
+
+  /// how far away is the starting position (where we place pieces after throwing 6)
+  /// from the ending position (= the last field before home)
+  fn start_end_position_difference(&self) -> usize {
+    2
+  }
+
+  // we can use this 'modulo trick' to deal with different offsets and looping (pos 39 -> 0)
+  // e.g. start_pos = 0 => end_pos = 39
+  pub fn get_end_position(&self) -> usize {
+    (self.get_starting_position() + BOARD_SIZE - self.start_end_position_difference()) % BOARD_SIZE
+  }
+
+  /// returns size of the home column (finish)
+  pub fn get_home_size(&self) -> usize {
+    HOME_SIZE
+  }
+
+  pub fn get_home_field(&self, home_offset: usize) -> Field {
+    match self.is_in_bounds_home(home_offset) {
+      true => self.get_home()[home_offset],
+      false => None,
+    }
+  }
+
+  /// there is a clock-wise ordering: Yellow, Blue, Red, Green
+  pub fn get_offset(&self) -> usize {
+    Self::offset_of(self.current_player)
+  }
+
+  pub(crate) fn offset_of(color: Color) -> usize {
+    let offset = BOARD_SIZE / 4;
+    match color {
+      Color::Yellow => 0,
+      Color::Blue => offset,
+      Color::Red => offset * 2,
+      Color::Green => offset * 3,
+    }
+  }
+
+  /// position of the field where we put pieces after throwing 6
+  pub fn get_starting_position(&self) -> usize {
+    self.get_offset() + 8
+  }
+
+  /// how many fields the pieces walked since `previous`, clients animate every field,
+  /// a captured piece returns to start in a single step together with the capturing one
+  pub fn animation_steps(&self, previous: &Game) -> usize {
+    self
+      .pieces()
+      .filter_map(|piece| {
+        let before = previous.get_piece(piece.id)?.location;
+        if before == piece.location {
+          return None;
+        }
+        let starting_position = Self::offset_of(piece.color) + 8;
+        let walked = piece
+          .location
+          .progress(starting_position)
+          .saturating_sub(before.progress(starting_position));
+        Some(walked.max(1))
+      })
+      .max()
+      .unwrap_or(0)
+  }
+
+  /// returns false if the delta doesn't directly follow this game,
+  /// the whole game has to be requested again in that case
+  pub fn apply_delta(&mut self, delta: GameDelta) -> bool {
+    if delta.sequence != self.sequence + 1 {
+      return false;
+    }
+
+    for change in delta.changes {
+      match change {
+        GameChange::PieceMoved { id, to, .. } => {
+          let piece = self
+            .players
+            .iter_mut()
+            .flat_map(|player| player.pieces.iter_mut())
+            .find(|piece| piece.id == id);
+          match piece {
+            Some(piece) => piece.location = to,
+            None => return false,
+          }
+        }
+        GameChange::CurrentPlayerChanged(color) => self.current_player = color,
+        GameChange::PhaseChanged(phase) => self.round_phase = phase,
+        GameChange::DiceThrowsChanged(dice_throws) => self.dice_throws = dice_throws,
+        GameChange::PlacementsChanged { placements, winner } => {
+          self.placements = placements;
+          self.winner = winner;
+        }
+      }
+    }
+    self.sequence = delta.sequence;
+    true
+  }
+
+  /// if we land on opponent at 'position', we remove his piece (we can't jump on our own piece)
+  pub fn clear_field(&mut self, position: usize) {
+    let location = PieceLocation::Board(position % BOARD_SIZE);
+    if let Some(color) = self.get_field(position) {
+      if let Some(piece) = self.get_player_mut(color).get_piece_mut(location) {
+        piece.location = PieceLocation::Start;
+      }
+    }
+  }
+
+  /// how far from the starting position a promoted piece lands, the 6 itself only brings
+  /// the piece to the board, so without split dice it needs the throw after the 6
+  fn promotion_steps(&self, dice_value: usize) -> Option<usize> {
+    match self.ruleset.split_dice {
+      true => (dice_value == 6).then_some(0),
+      false => (dice_value > 6).then(|| dice_value - 6),
+    }
+  }
+
+  /// check if position where promoted piece would land is not occupied by our piece
+  pub fn can_promote_piece(&self, dice_value: usize) -> bool {
+    match self.promotion_steps(dice_value) {
+      Some(steps) => {
+        self.is_available_field(self.get_starting_position() + steps)
+          && self.get_current_player().pawns_at_start() > 0
+      }
+      None => false,
+    }
+  }
+
+  /// we can jump to a field, if it's either empty or occupied by opponent,
+  /// i.e. it's not occupied by us
+  pub fn is_available_field(&self, position: usize) -> bool {
+    let position = position % BOARD_SIZE;
+    !self.is_current_players_piece(position)
+  }
+
+  pub fn get_new_position(&self, position: usize, dice_value: usize) -> usize {
+    (position + dice_value) % BOARD_SIZE
+  }
+
+  /// if we can make a move/jump within main board/field (not reaching home)
+  pub fn can_jump(&self, position: usize, dice_value: usize) -> bool {
+    dice_value < self.distance_from_home(position)
+      && self.is_available_field(self.get_new_position(position, dice_value))
+  }
+
+  pub fn is_in_bounds_home(&self, home_offset: usize) -> bool {
+    home_offset < self.get_home_size()
+  }
+
+  pub fn is_available_home_field(&self, home_offset: usize) -> bool {
+    self.is_in_bounds_home(home_offset) && !self.is_home_field_occupied(home_offset)
+  }
+
+  /// check if piece can reach `safe zone`
+  pub fn can_jump_to_home(&self, position: usize, dice_value: usize) -> bool {
+    match self.can_reach_home(position, dice_value)
+      && !self.would_overjump_home(position, dice_value)
+    {
+      true => self.is_available_home_field(self.get_home_offset(position, dice_value)),
+      false => false,
+    }
+  }
+
+  // if we move 'dice_value' fields, we will reach beyond the main board/field
+  pub fn can_reach_home(&self, position: usize, dice_value: usize) -> bool {
+    dice_value >= self.distance_from_home(position)
+  }
+
+  // if we move 'dice_value' fields, we will reach beyond the main board/field
+  /// assumes position indexes game.fields()
+  pub fn can_jump_to_finish(&self, position: usize, dice_value: usize) -> bool {
+    dice_value == self.distance_from_home(position) + self.get_home_size()
+  }
+
+  pub fn would_overjump_home(&self, position: usize, dice_value: usize) -> bool {
+    // distance_from_home gets you already to the first home field, that's why '>=' and not only '>'
+    dice_value >= self.distance_from_home(position) + self.get_home_size()
+  }
+
+  /// returns position/index of field in player's home column where we will jump,
+  /// i.e. offset in player's home column
+  /// e.g. if piece is right in front of home => distance = 1, and if we throw a 1,
+  ///      we would reach the first home field (home_offset = 0)
+  pub fn get_home_offset(&self, position: usize, dice_value: usize) -> usize {
+    dice_value - self.distance_from_home(position)
+  }
+
+  pub fn get_home(&self) -> Vec<Field> {
+    self.get_current_player().home()
+  }
+
+  pub fn is_home_field_occupied(&self, home_offset: usize) -> bool {
+    self.get_home_field(home_offset).is_some()
+  }
+
+  /// can jump from home (at home_offset) to finish
+  pub fn can_jump_from_home_to_finish(&self, home_offset: usize, dice_value: usize) -> bool {
+    let home = self.get_home();
+    home_offset + dice_value == home.len()
+  }
+
+  /// every move the current player can make with `dice_value`,
+  /// only one promotion is listed since pieces at start are interchangeable
+  pub fn legal_moves(&self, dice_value: usize) -> Vec<Move> {
+    let player = self.get_current_player();
+    let mut moves = Vec::new();
+
+    let piece_at_start = player
+      .pieces
+      .iter()
+      .find(|piece| piece.location == PieceLocation::Start);
+    if let Some(piece) = piece_at_start {
+      if let (true, Some(steps)) = (
+        self.can_promote_piece(dice_value),
+        self.promotion_steps(dice_value),
+      ) {
+        let to = self.get_new_position(self.get_starting_position(), steps);
+        moves.push(Move::Promote {
+          piece_id: piece.id,
+          to,
+          captures: self.is_opponents_piece(to),
+        });
+      }
+    }
+
+    for piece in &player.pieces {
+      let piece_id = piece.id;
+      let piece_move = match piece.location {
+        PieceLocation::Board(from) if self.can_jump_to_finish(from, dice_value) => {
+          Some(Move::Finish {
+            piece_id,
+            from: piece.location,
+          })
+        }
+        PieceLocation::Board(from) if self.can_jump_to_home(from, dice_value) => {
+          Some(Move::EnterHome {
+            piece_id,
+            from,
+            to: self.get_home_offset(from, dice_value),
+          })
+        }
+        PieceLocation::Board(from) if self.can_jump(from, dice_value) => {
+          let to = self.get_new_position(from, dice_value);
+          Some(Move::Advance {
+            piece_id,
+            from,
+            to,
+            captures: self.is_opponents_piece(to),
+          })
+        }
+        PieceLocation::Home(from) if self.can_jump_from_home_to_finish(from, dice_value) => {
+          Some(Move::Finish {
+            piece_id,
+            from: piece.location,
+          })
+        }
+        PieceLocation::Home(from) if self.is_available_home_field(from + dice_value) => {
+          Some(Move::HomeAdvance {
+            piece_id,
+            from,
+            to: from + dice_value,
+          })
+        }
+        _ => None,
+      };
+      moves.extend(piece_move);
+    }
+    moves
+  }
+
+  /// legal moves for every die which can still be used, see `Ruleset::split_dice`
+  pub fn legal_moves_per_die(&self) -> Vec<DieMoves> {
+    self
+      .dice_throws
+      .iter()
+      .enumerate()
+      .map(|(die_index, &value)| DieMoves {
+        die_index,
+        value,
+        moves: self.legal_moves(value),
+      })
+      .filter(|die_moves| !die_moves.moves.is_empty())
+      .collect()
+  }
+
+  /// whether the current player can move with the thrown dice
+  pub fn has_legal_moves(&self) -> bool {
+    match self.ruleset.split_dice {
+      true => !self.legal_moves_per_die().is_empty(),
+      false => !self.legal_moves(self.dice_throws.iter().sum()).is_empty(),
+    }
+  }
+
+  /// why a piece has no legal move for `dice_value`
+  fn illegal_move_reason(&self, piece_id: usize, dice_value: usize) -> IllegalMoveReason {
+    let location = match self.get_piece(piece_id) {
+      Some(piece) if piece.color == self.current_player => piece.location,
+      _ => return IllegalMoveReason::NotYourPiece,
+    };
+    match location {
+      PieceLocation::Start => IllegalMoveReason::CannotPromote,
+      PieceLocation::Board(position) if self.would_overjump_home(position, dice_value) => {
+        IllegalMoveReason::OverjumpsHome
+      }
+      PieceLocation::Board(position) if self.can_reach_home(position, dice_value) => {
+        IllegalMoveReason::HomeFieldOccupied
+      }
+      PieceLocation::Board(_) => IllegalMoveReason::FieldOccupied,
+      PieceLocation::Home(home_offset) if home_offset + dice_value > self.get_home_size() => {
+        IllegalMoveReason::OverjumpsHome
+      }
+      PieceLocation::Home(_) => IllegalMoveReason::HomeFieldOccupied,
+      PieceLocation::Finished => IllegalMoveReason::PieceFinished,
+    }
+  }
+
+  /// executes one of the `legal_moves` for `dice_value`, any other move is rejected
+  pub fn execute_move(&mut self, piece_move: Move, dice_value: usize) -> MoveResult {
+    if !self.legal_moves(dice_value).contains(&piece_move) {
+      return MoveResult::Error(self.illegal_move_reason(piece_move.piece_id(), dice_value));
+    }
+
+    let destination = piece_move.destination();
+    let mut captured = None;
+    if let PieceLocation::Board(position) = destination {
+      // we can't land on our own piece, so this removes only opponents
+      captured = self.get_field(position);
+      self.clear_field(position);
+    }
+    self.set_piece_location(piece_move.piece_id(), destination);
+    self.log_move(piece_move, captured);
+
+    let message = match piece_move {
+      Move::Promote { .. } => "Your piece has been promoted!",
+      Move::Advance { .. } => "Moved to a new position.",
+      Move::EnterHome { .. } => "Successfully moved a piece to home!",
+      Move::Finish { .. } => "Jumped to finish!",
+      Move::HomeAdvance { .. } => "Move successful.",
+    };
+    MoveResult::Success(String::from(message))
+  }
+
+  /// the turn is opened with the first move, while all the dice of the turn are known
+  fn log_move(&mut self, piece_move: Move, captured: Option<Color>) {
+    let (color, dice) = (self.current_player, self.dice_throws.clone());
+    self
+      .current_turn
+      .get_or_insert_with(|| Turn::new(color, dice))
+      .moves
+      .push(TurnMove {
+        piece_move,
+        captured,
+      });
+  }
+
+  pub fn promote_piece(&mut self, dice_value: usize) -> MoveResult {
+    let promotion = self
+      .legal_moves(dice_value)
+      .into_iter()
+      .find(|piece_move| matches!(piece_move, Move::Promote { .. }));
+    match promotion {
+      Some(promotion) => self.execute_move(promotion, dice_value),
+      None => MoveResult::Error(IllegalMoveReason::CannotPromote),
+    }
+  }
+
+  /// moves a piece of the current player by `dice_value`, pieces at start are promoted
+  pub fn move_piece(&mut self, piece_id: usize, dice_value: usize) -> MoveResult {
+    match self.get_piece(piece_id) {
+      Some(piece) if piece.color == self.current_player => {
+        if piece.location == PieceLocation::Start {
+          return self.promote_piece(dice_value);
+        }
+      }
+      _ => return MoveResult::Error(IllegalMoveReason::NotYourPiece),
+    }
+
+    let piece_move = self
+      .legal_moves(dice_value)
+      .into_iter()
+      .find(|piece_move| piece_move.piece_id() == piece_id);
+    match piece_move {
+      Some(piece_move) => self.execute_move(piece_move, dice_value),
+      None => MoveResult::Error(self.illegal_move_reason(piece_id, dice_value)),
+    }
+  }
+
+  /// moves a piece by a single die of the throw, the die can't be used again
+  pub fn move_piece_with_die(&mut self, piece_id: usize, die_index: usize) -> MoveResult {
+    let dice_value = match self.dice_throws.get(die_index) {
+      Some(dice_value) => *dice_value,
+      None => return MoveResult::Error(IllegalMoveReason::NoSuchDie),
+    };
+    let move_result = self.move_piece(piece_id, dice_value);
+    if let MoveResult::Success(_) = move_result {
+      self.dice_throws.remove(die_index);
+    }
+    move_result
+  }
+
+  /// returns whether a field specified by <position> is is occupied by a piece with <color>
+  pub fn is_players_piece(&self, position: usize, player_color: Color) -> bool {
+    match self.get_field(position) {
+      Some(color) => color == player_color,
+      _ => false,
+    }
+  }
+
+  pub fn is_opponents_piece(&self, position: usize) -> bool {
+    match self.get_field(position) {
+      Some(color) => color != self.current_player,
+      _ => false,
+    }
+  }
+
+  pub fn is_current_players_piece(&self, position: usize) -> bool {
+    self.is_players_piece(position, self.current_player)
+  }
+
+  pub fn get_player(&self, player_color: Color) -> &Player {
+    self
+      .players
+      .iter()
+      .find(|&player| player.color == player_color)
+      .unwrap()
+  }
+
+  pub fn get_player_mut(&mut self, player_color: Color) -> &mut Player {
+    self
+      .players
+      .iter_mut()
+      .find(|player| player.color == player_color)
+      .unwrap()
+  }
+
+  pub fn get_current_player(&self) -> &Player {
+    self.get_player(self.current_player)
+  }
+
+  pub fn get_current_player_mut(&mut self) -> &mut Player {
+    self.get_player_mut(self.current_player)
+  }
+
+  pub fn is_player_ai(&self, player_color: Color) -> bool {
+    self
+      .players
+      .iter()
+      .any(|player| player.is_bot && player.color == player_color)
+  }
+
+  pub fn is_current_player_ai(&self) -> bool {
+    self.is_player_ai(self.current_player)
+  }
+
+  pub fn get_player_by_id(&self, id: &str) -> Option<&Player> {
+    self.players.iter().find(|player| player.id == id)
+  }
+}
+
+// ----------------[ tests ]-----------------
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::borrow::Borrow;
+
+  fn get_empty_game() -> Game {
+    let mut game = Game::new();
+    let mut players = Vec::new();
+    for color in get_colors() {
+      players.push(Player::new("".into(), "".into(), color, false));
+    }
+    game.players = players;
+    game
+  }
+
+  fn get_colors() -> Vec<Color> {
+    vec![Color::Yellow, Color::Blue, Color::Red, Color::Green]
+  }
+
+  fn get_all_players(game: &Game) -> Vec<&Player> {
+    get_colors()
+      .iter()
+      .map(|color| game.get_player(*color))
+      .collect::<Vec<&Player>>()
+  }
+
+  fn is_empty_fields(fields: &Vec<Field>) -> bool {
+    fields.iter().all(|field| field.is_none())
+  }
+
+  fn empty_fields_count(fields: &Vec<Field>) -> usize {
+    fields
+      .iter()
+      .filter(|&field| field.is_none())
+      .collect::<Vec<&Field>>()
+      .len()
+  }
+
+  fn is_empty_field(fields: &Vec<Field>, position: usize) -> bool {
+    fields.get(position).is_none()
+  }
+
+  fn is_occupied_field_by(fields: &Vec<Field>, position: usize, color: Color) -> bool {
+    match fields.get(position) {
+      Some(Some(_color)) => *_color == color,
+      _ => false,
+    }
+  }
+
+  fn fields(game: &Game) -> Vec<Field> {
+    (0..game.field_size())
+      .map(|position| game.get_field(position))
+      .collect()
+  }
+
+  /// takes a piece from start of the player and puts it to `location`
+  fn place_piece(player: &mut Player, location: PieceLocation) {
+    player.get_piece_mut(PieceLocation::Start).unwrap().location = location;
+  }
+
+  fn finish_pieces(player: &mut Player, count: usize) {
+    for _ in 0..count {
+      place_piece(player, PieceLocation::Finished);
+    }
+  }
+
+  /// replaces whatever is on the board at `position`, like assigning to the old fields vector
+  fn set_field(game: &mut Game, position: usize, field: Field) {
+    game.clear_field(position);
+    if let Some(color) = field {
+      place_piece(game.get_player_mut(color), PieceLocation::Board(position));
+    }
+  }
+
+  /// moves the current player's piece standing at `position` of the board or the home column
+  fn move_from(
+    game: &mut Game,
+    position: usize,
+    dice_value: usize,
+    home_column: bool,
+  ) -> MoveResult {
+    let location = match home_column {
+      true => PieceLocation::Home(position),
+      false => PieceLocation::Board(position),
+    };
+    let piece = game
+      .get_current_player()
+      .pieces
+      .iter()
+      .find(|piece| piece.location == location);
+    match piece.map(|piece| piece.id) {
+      Some(piece_id) => game.move_piece(piece_id, dice_value),
+      None => MoveResult::Error(IllegalMoveReason::NotYourPiece),
+    }
+  }
+
+  /// positions of pieces on the board and in the home column which can move, and whether we can promote
+  fn get_available_positions(game: &Game, dice_value: usize) -> (Vec<usize>, Vec<usize>, bool) {
+    let moves = game.legal_moves(dice_value);
+    let board = moves
+      .iter()
+      .filter_map(|piece_move| match piece_move.origin() {
+        PieceLocation::Board(position) => Some(position),
+        _ => None,
+      })
+      .collect();
+    let home = moves
+      .iter()
+      .filter_map(|piece_move| match piece_move.origin() {
+        PieceLocation::Home(home_offset) => Some(home_offset),
+        _ => None,
+      })
+      .collect();
+    let can_promote = moves
+      .iter()
+      .any(|piece_move| matches!(piece_move, Move::Promote { .. }));
+    (board, home, can_promote)
+  }
+
+  fn print_game(game: &Game) {
+    println!();
+    for (i, field) in fields(game).iter().enumerate() {
+      println!("{}: {:?}", i, field);
+    }
+    println!();
+  }
+
+  fn print_home(player: &Player) {
+    println!();
+    for (i, field) in player.home().iter().enumerate() {
+      println!("{}: {:?}", i, field);
+    }
+    println!();
+  }
+
+  #[test]
+  fn initial_promote() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    for player in get_all_players(&game) {
+      assert_eq!(player.pawns_at_start(), 4);
+      assert_eq!(player.pawns_at_finish(), 0);
+      assert!(is_empty_fields(&player.home()));
+    }
+
+    // the starting player is Yellow
+    assert_eq!(game.current_player, Color::Yellow);
+    assert!(is_empty_fields(&fields(&game)));
+    assert_eq!(game.get_starting_position(), 8); // Yellow player starts at 8
+
+    let dice_value = 9;
+    let position = 0;
+    let home_column = false;
+
+    match move_from(&mut game, position, dice_value, home_column) {
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Error(_) => assert!(false),
+      MoveResult::Success(_) => assert!(true),
+    }
+
+    assert!(is_empty_field(&fields(&game), game.get_starting_position()));
+    assert!(is_occupied_field_by(
+      &fields(&game),
+      game.get_starting_position() + 3,
+      Color::Yellow
+    ));
+    assert!(is_occupied_field_by(&fields(&game), 11, Color::Yellow));
+    assert!(!(is_empty_fields(&fields(&game))));
+    assert_eq!(game.get_current_player().pawns_at_start(), 3);
+
+    game.update_current_player();
+
+    assert_eq!(game.current_player, Color::Blue)
+  }
+
+  #[test]
+  fn blocked_promotion() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    assert_eq!(game.get_starting_position(), 8);
+    set_field(&mut game, 8 + 3, Some(Color::Yellow));
+    assert_eq!(empty_fields_count(&fields(&game)), game.field_size() - 1);
+
+    match move_from(&mut game, 0, 6 + 3, false) {
+      MoveResult::Error(_) => assert!(true),
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Success(_) => assert!(false),
+    }
+
+    assert_eq!(game.get_current_player().pawns_at_start(), 4);
+    assert!(is_empty_field(&fields(&game), 8));
+    assert!(is_occupied_field_by(&fields(&game), 8 + 3, Color::Yellow));
+    assert_eq!(empty_fields_count(&fields(&game)), game.field_size() - 1);
+  }
+
+  #[test]
+  fn promotion_remove_opponent() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    let dice_value = 6 + 8;
+    let opponent_color = Color::Green;
+    let starting_pos = game.get_starting_position();
+    let field_size = game.field_size();
+
+    set_field(
+      &mut game,
+      starting_pos + dice_value - 6,
+      Some(opponent_color),
+    );
+
+    // set_field(&mut game, starting_pos + dice_value - 6, Some(opponent_color));
+    assert_eq!(game.get_player(opponent_color).pawns_at_start(), 3);
+
+    print_game(&game);
+
+    let mut game = game.clone();
+    match move_from(&mut game, 0, dice_value, false) {
+      MoveResult::Error(_) => assert!(false),
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Success(_) => assert!(true),
+    }
+
+    print_game(&game);
+
+    assert_eq!(game.get_current_player().pawns_at_start(), 3);
+    assert_eq!(game.get_player(opponent_color).pawns_at_start(), 4);
+    assert!(is_empty_field(&fields(&game), 8));
+    assert!(is_occupied_field_by(&fields(&game), 8 + 8, Color::Yellow));
+    assert_eq!(empty_fields_count(&fields(&game)), field_size - 1);
+  }
+
+  #[test]
+  fn remove_opponent() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    let dice_value = 5;
+    let opponent_color = Color::Green;
+    let starting_pos = 20;
+    let field_size = game.field_size();
+
+    set_field(&mut game, starting_pos, Some(Color::Yellow));
+    set_field(&mut game, starting_pos + dice_value, Some(opponent_color));
+    set_field(
+      &mut game,
+      starting_pos + dice_value + 1,
+      Some(opponent_color),
+    );
+    set_field(
+      &mut game,
+      starting_pos + dice_value - 1,
+      Some(opponent_color),
+    );
+
+    assert_eq!(game.get_player(opponent_color).pawns_at_start(), 1);
+    assert_eq!(game.get_current_player().pawns_at_start(), 3);
+
+    print_game(&game);
+
+    let mut game = game.clone();
+    match move_from(&mut game, starting_pos, dice_value, false) {
+      MoveResult::Error(_) => assert!(false),
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Success(_) => assert!(true),
+    }
+
+    print_game(&game);
+
+    assert_eq!(game.get_current_player().pawns_at_start(), 3);
+    assert_eq!(game.get_player(opponent_color).pawns_at_start(), 2);
+    assert!(is_empty_field(&fields(&game), starting_pos));
+    assert!(is_occupied_field_by(
+      &fields(&game),
+      starting_pos + dice_value,
+      Color::Yellow
+    ));
+    assert!(is_occupied_field_by(
+      &fields(&game),
+      starting_pos + dice_value + 1,
+      Color::Green
+    ));
+    assert!(is_occupied_field_by(
+      &fields(&game),
+      starting_pos + dice_value - 1,
+      Color::Green
+    ));
+
+    // normally, Blue would follow
+    game.current_player = Color::Green;
+
+    let dice_value = 1;
+    let starting_pos = 24;
+
+    let mut game = game.clone();
+    match move_from(&mut game, starting_pos, dice_value, false) {
+      MoveResult::Error(_) => assert!(false),
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Success(_) => assert!(true),
+    }
+
+    print_game(&game);
+
+    assert_eq!(game.get_player(Color::Green).pawns_at_start(), 2);
+    assert!(is_empty_field(&fields(&game), starting_pos));
+    assert!(is_occupied_field_by(
+      &fields(&game),
+      starting_pos + dice_value,
+      Color::Green
+    ));
+    assert!(is_occupied_field_by(
+      &fields(&game),
+      starting_pos + dice_value + 1,
+      Color::Green
+    ));
+  }
+
+  #[test]
+  fn move_board_to_home_success() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    let dice_value = 1;
+    let starting_pos = 6; // right in front of home
+    set_field(&mut game, starting_pos, Some(Color::Yellow));
+
+    let mut game = game.clone();
+    match move_from(&mut game, starting_pos, dice_value, false) {
+      MoveResult::Error(_) => assert!(false),
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Success(_) => assert!(true),
+    }
+
+    let player = game.get_current_player();
+    assert!(is_empty_field(&fields(&game), starting_pos));
+    assert!(is_occupied_field_by(&player.home(), 0, Color::Yellow));
+  }
+
+  #[test]
+  fn move_board_to_home_overjump() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    let dice_value = 9;
+    let starting_pos = 6; // right in front of home
+    set_field(&mut game, starting_pos, Some(Color::Yellow));
+
+    let mut game = game.clone();
+    match move_from(&mut game, starting_pos, dice_value, false) {
+      MoveResult::Error(_) => assert!(true),
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Success(_) => assert!(false),
+    }
+
+    let player = game.get_current_player();
+    assert!(is_occupied_field_by(
+      &fields(&game),
+      starting_pos,
+      Color::Yellow
+    ));
+    assert!(is_empty_fields(&player.home()));
+  }
+
+  #[test]
+  fn move_board_to_home_blocked() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    let dice_value = 1;
+    let starting_pos = 6; // right in front of home
+    set_field(&mut game, starting_pos, Some(Color::Yellow));
+    let player = game.get_current_player_mut();
+    place_piece(player, PieceLocation::Home(0));
+
+    let mut game = game.clone();
+    match move_from(&mut game, starting_pos, dice_value, false) {
+      MoveResult::Error(_) => assert!(true),
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Success(_) => assert!(false),
+    }
+
+    let player = game.get_current_player();
+    assert!(is_occupied_field_by(
+      &fields(&game),
+      starting_pos,
+      Color::Yellow
+    ));
+    assert!(is_occupied_field_by(&player.home(), 0, Color::Yellow));
+  }
+
+  #[test]
+  fn move_home_to_home() {}
+
+  #[test]
+  fn move_home_to_home_blocked() {}
+
+  #[test]
+  fn move_board_to_finish() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    let dice_value = 6;
+    let starting_pos = 6; // right in front of home
+    set_field(&mut game, starting_pos, Some(Color::Yellow));
+
+    let mut game = game.clone();
+    match move_from(&mut game, starting_pos, dice_value, false) {
+      MoveResult::Error(_) => assert!(false),
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Success(_) => assert!(true),
+    }
+
+    let player = game.get_current_player();
+    assert!(is_empty_field(&fields(&game), starting_pos));
+    assert_eq!(player.pawns_at_finish(), 1);
+  }
+
+  #[test]
+  fn move_home_to_finish() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    let dice_value = 3;
+    let starting_pos = 2;
+    let player = game.get_current_player_mut();
+    place_piece(player, PieceLocation::Home(starting_pos));
+
+    let mut game = game.clone();
+    match move_from(&mut game, starting_pos, dice_value, true) {
+      MoveResult::Error(_) => assert!(false),
+      MoveResult::Winner(_) => assert!(false),
+      MoveResult::Success(_) => assert!(true),
+    }
+
+    let player = game.get_current_player();
+    assert!(is_empty_field(&player.home(), starting_pos));
+    assert_eq!(player.pawns_at_finish(), 1);
+  }
+
+  #[test]
+  fn move_to_finish_check_winner() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    let dice_value = 3;
+    let starting_pos = 2;
+    let player = game.get_current_player_mut();
+    place_piece(player, PieceLocation::Home(starting_pos));
+    finish_pieces(player, 3);
+
+    let mut game = game.clone();
+    match move_from(&mut game, starting_pos, dice_value, true) {
+      MoveResult::Error(_) => assert!(false),
+      MoveResult::Winner(_) => assert!(true),
+      MoveResult::Success(_) => assert!(true),
+    }
+
+    let winner = game.check_winner();
+    assert!(winner.is_some());
+    assert_eq!(winner.unwrap(), Color::Yellow);
+  }
+
+  #[test]
+  fn invalid_moves() {}
+
+  fn compare_vectors(a: &Vec<usize>, b: &Vec<usize>) {
+    assert_eq!(a.len(), b.len());
+    for n in a {
+      assert!(b.contains(n));
+    }
+  }
+
+  #[test]
+  fn available_positions() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    // Yellow starts at position 8, the field in front of home is at position 6
+    set_field(&mut game, 9, Some(Color::Yellow));
+    set_field(&mut game, 12, Some(Color::Yellow));
+    set_field(&mut game, 6, Some(Color::Yellow));
+
+    let yellow_player = game.get_player_mut(Color::Yellow);
+    place_piece(yellow_player, PieceLocation::Home(2));
+    assert_eq!(yellow_player.pawns_at_start(), 0);
+
+    let dice_value = 1;
+    let (actual_board_pos, actual_home_pos, actual_can_promote) =
+      get_available_positions(&game, dice_value);
+    let (expected_board_pos, expected_home_pos, expected_can_promote): (
+      Vec<usize>,
+      Vec<usize>,
+      bool,
+    ) = (vec![6, 9, 12], vec![2], false);
+    compare_vectors(&actual_board_pos, &expected_board_pos);
+    compare_vectors(&actual_home_pos, &expected_home_pos);
+    assert_eq!(actual_can_promote, expected_can_promote);
+
+    let dice_value = 3;
+    let (actual_board_pos, actual_home_pos, actual_can_promote) =
+      get_available_positions(&game, dice_value);
+    let (expected_board_pos, expected_home_pos, expected_can_promote): (
+      Vec<usize>,
+      Vec<usize>,
+      bool,
+    ) = (vec![12], vec![2], false);
+    compare_vectors(&actual_board_pos, &expected_board_pos);
+    compare_vectors(&actual_home_pos, &expected_home_pos);
+    assert_eq!(actual_can_promote, expected_can_promote);
+
+    let dice_value = 4;
+    let (actual_board_pos, actual_home_pos, actual_can_promote) =
+      get_available_positions(&game, dice_value);
+    let (expected_board_pos, expected_home_pos, expected_can_promote): (
+      Vec<usize>,
+      Vec<usize>,
+      bool,
+    ) = (vec![6, 9, 12], vec![], false);
+    compare_vectors(&actual_board_pos, &expected_board_pos);
+    compare_vectors(&actual_home_pos, &expected_home_pos);
+    assert_eq!(actual_can_promote, expected_can_promote);
+
+    let dice_value = 6;
+    let (actual_board_pos, actual_home_pos, actual_can_promote) =
+      get_available_positions(&game, dice_value);
+    let (expected_board_pos, expected_home_pos, expected_can_promote): (
+      Vec<usize>,
+      Vec<usize>,
+      bool,
+    ) = (vec![6, 9, 12], vec![], false);
+    compare_vectors(&actual_board_pos, &expected_board_pos);
+    compare_vectors(&actual_home_pos, &expected_home_pos);
+    assert_eq!(actual_can_promote, expected_can_promote);
+  }
+
+  #[test]
+  fn available_positions_promote() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    // Yellow starts at position 8
+    set_field(&mut game, 9, Some(Color::Yellow));
+
+    assert_eq!(game.get_player(Color::Yellow).pawns_at_start(), 3);
+
+    let dice_value = 11;
+    let (actual_board_pos, actual_home_pos, actual_can_promote) =
+      get_available_positions(&game, dice_value);
+    let (expected_board_pos, expected_home_pos, expected_can_promote): (
+      Vec<usize>,
+      Vec<usize>,
+      bool,
+    ) = (vec![9], vec![], true);
+    compare_vectors(&actual_board_pos, &expected_board_pos);
+    compare_vectors(&actual_home_pos, &expected_home_pos);
+    assert_eq!(actual_can_promote, expected_can_promote);
+
+    // blocked by our piece
+    let dice_value = 7;
+    let (actual_board_pos, actual_home_pos, actual_can_promote) =
+      get_available_positions(&game, dice_value);
+    let (expected_board_pos, expected_home_pos, expected_can_promote): (
+      Vec<usize>,
+      Vec<usize>,
+      bool,
+    ) = (vec![9], vec![], false);
+    compare_vectors(&actual_board_pos, &expected_board_pos);
+    compare_vectors(&actual_home_pos, &expected_home_pos);
+    assert_eq!(actual_can_promote, expected_can_promote);
+
+    // // if there is an opponent piece, we don't get blocked (promotion)
+    set_field(&mut game, 9, Some(Color::Green));
+    let dice_value = 7;
+    let (actual_board_pos, actual_home_pos, actual_can_promote) =
+      get_available_positions(&game, dice_value);
+    let (expected_board_pos, expected_home_pos, expected_can_promote): (
+      Vec<usize>,
+      Vec<usize>,
+      bool,
+    ) = (vec![], vec![], true);
+    compare_vectors(&actual_board_pos, &expected_board_pos);
+    compare_vectors(&actual_home_pos, &expected_home_pos);
+    assert_eq!(actual_can_promote, expected_can_promote);
+
+    // if there is an opponent piece, we don't get blocked (on board)
+    set_field(&mut game, 9, Some(Color::Yellow));
+    set_field(&mut game, 12, Some(Color::Green));
+    let dice_value = 3;
+    let (actual_board_pos, actual_home_pos, actual_can_promote) =
+      get_available_positions(&game, dice_value);
+    let (expected_board_pos, expected_home_pos, expected_can_promote): (
+      Vec<usize>,
+      Vec<usize>,
+      bool,
+    ) = (vec![9], vec![], false);
+    compare_vectors(&actual_board_pos, &expected_board_pos);
+    compare_vectors(&actual_home_pos, &expected_home_pos);
+    assert_eq!(actual_can_promote, expected_can_promote);
+  }
+
+  #[test]
+  fn split_dice_moves_with_each_die() {
+    let mut game = get_empty_game();
+    game.ruleset.split_dice = true;
+    game.current_player = Color::Yellow;
+    game.dice_throws = vec![6, 3];
+
+    // the 6 alone brings a piece to the starting position, the 3 can't be used yet
+    let per_die = game.legal_moves_per_die();
+    assert_eq!(per_die.len(), 1);
+    assert_eq!(per_die[0].die_index, 0);
+    let piece_id = per_die[0].moves[0].piece_id();
+
+    assert!(matches!(
+      game.move_piece_with_die(piece_id, 0),
+      MoveResult::Success(_)
+    ));
+    assert_eq!(game.dice_throws, vec![3]);
+    assert!(is_occupied_field_by(&fields(&game), 8, Color::Yellow));
+
+    assert!(matches!(
+      game.move_piece_with_die(piece_id, 0),
+      MoveResult::Success(_)
+    ));
+    assert!(game.dice_throws.is_empty());
+    assert!(is_occupied_field_by(&fields(&game), 11, Color::Yellow));
+    assert!(!game.has_legal_moves());
+  }
+
+  #[test]
+  fn animation_steps_follow_the_path() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+    place_piece(game.get_player_mut(Color::Blue), PieceLocation::Board(6));
+    place_piece(game.get_current_player_mut(), PieceLocation::Board(4));
+    let piece_id = game.get_current_player().pieces[0].id;
+
+    // the captured piece returns to start while the capturing one walks two fields
+    let previous = game.clone();
+    assert!(matches!(
+      game.move_piece(piece_id, 2),
+      MoveResult::Success(_)
+    ));
+    assert_eq!(game.animation_steps(&previous), 2);
+
+    // the field in front of home is followed by the home column
+    let previous = game.clone();
+    assert!(matches!(
+      game.move_piece(piece_id, 3),
+      MoveResult::Success(_)
+    ));
+    assert_eq!(
+      game.get_piece(piece_id).unwrap().location,
+      PieceLocation::Home(2)
+    );
+    assert_eq!(game.animation_steps(&previous), 3);
+    assert_eq!(game.animation_steps(&game.clone()), 0);
+  }
+
+  #[test]
+  fn placements_first_winner_ends_game() {
+    let mut game = get_empty_game();
+    game.current_player = Color::Yellow;
+
+    let player = game.get_current_player_mut();
+    finish_pieces(player, 4);
+
+    assert_eq!(game.update_placements(), Some(Color::Yellow));
+    assert_eq!(game.placements, vec![Color::Yellow]);
+  }
+
+  #[test]
+  fn placements_full_ranking() {
+    let mut game = get_empty_game();
+    game.ruleset.full_ranking = true;
+    game.current_player = Color::Yellow;
+
+    let player = game.get_current_player_mut();
+    finish_pieces(player, 4);
+
+    // the game goes on and the finished player is skipped
+    assert_eq!(game.update_placements(), None);
+    assert_eq!(game.placements, vec![Color::Yellow]);
+    game.current_player = Color::Green;
+    game.update_current_player();
+    assert_eq!(game.current_player, Color::Blue);
+
+    for color in [Color::Blue, Color::Red] {
+      let player = game.get_player_mut(color);
+      finish_pieces(player, 4);
+      game.update_placements();
+    }
+
+    // the last player is placed automatically
+    assert_eq!(game.placements.len(), 4);
+    assert_eq!(game.placements.first(), Some(&Color::Yellow));
+    assert_eq!(game.placements.last(), Some(&Color::Green));
+  }
+}
@@ -0,0 +1,133 @@
+use serde::{Deserialize, Serialize};
+
+use super::{color::Color, game::Game, piece::PieceLocation};
+use crate::utils::enums::RoundPhase;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum GameChange {
+  /// covers promotions, captures (back to `Start`) and finishing as well
+  PieceMoved {
+    id: usize,
+    from: PieceLocation,
+    to: PieceLocation,
+  },
+  CurrentPlayerChanged(Color),
+  PhaseChanged(RoundPhase),
+  DiceThrowsChanged(Vec<usize>),
+  PlacementsChanged {
+    placements: Vec<Color>,
+    winner: Option<Color>,
+  },
+}
+
+/// changes between two consecutive broadcasts of a game, clients apply the delta only if
+/// `sequence` directly follows the sequence of their game, otherwise they ask for a resync
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GameDelta {
+  pub sequence: u64,
+  pub changes: Vec<GameChange>,
+}
+
+impl GameDelta {
+  /// `None` if the games don't share the same players and pieces,
+  /// the full game has to be sent in that case
+  pub fn between(previous: &Game, current: &Game) -> Option<GameDelta> {
+    if previous.players.len() != current.players.len() || previous.started != current.started {
+      return None;
+    }
+
+    let mut changes = Vec::new();
+    for piece in current.pieces() {
+      let from = previous.get_piece(piece.id)?.location;
+      if from != piece.location {
+        changes.push(GameChange::PieceMoved {
+          id: piece.id,
+          from,
+          to: piece.location,
+        });
+      }
+    }
+
+    if previous.current_player != current.current_player {
+      changes.push(GameChange::CurrentPlayerChanged(current.current_player));
+    }
+    if previous.round_phase != current.round_phase {
+      changes.push(GameChange::PhaseChanged(current.round_phase));
+    }
+    if previous.dice_throws != current.dice_throws {
+      changes.push(GameChange::DiceThrowsChanged(current.dice_throws.clone()));
+    }
+    if previous.placements != current.placements || previous.winner != current.winner {
+      changes.push(GameChange::PlacementsChanged {
+        placements: current.placements.clone(),
+        winner: current.winner,
+      });
+    }
+
+    Some(GameDelta {
+      sequence: current.sequence,
+      changes,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::player::Player;
+
+  fn get_game() -> Game {
+    let mut game = Game::new();
+    game.players = vec![
+      Player::new("".into(), "".into(), Color::Yellow, false),
+      Player::new("".into(), "".into(), Color::Blue, false),
+    ];
+    game.current_player = Color::Yellow;
+    game
+  }
+
+  #[test]
+  fn promote_and_move_are_described_by_delta() {
+    let previous = get_game();
+    let mut current = previous.clone();
+    current.promote_piece(7);
+    current.sequence = 1;
+
+    let delta = GameDelta::between(&previous, &current).unwrap();
+    let piece = current.get_player(Color::Yellow).pieces[0].clone();
+    let start = current.get_starting_position() + 1;
+    assert_eq!(delta.sequence, 1);
+    assert_eq!(
+      delta.changes,
+      vec![GameChange::PieceMoved {
+        id: piece.id,
+        from: PieceLocation::Start,
+        to: PieceLocation::Board(start),
+      }]
+    );
+
+    let previous = current.clone();
+    current.move_piece(piece.id, 3);
+    current.update_current_player();
+    let delta = GameDelta::between(&previous, &current).unwrap();
+    assert_eq!(
+      delta.changes,
+      vec![
+        GameChange::PieceMoved {
+          id: piece.id,
+          from: PieceLocation::Board(start),
+          to: PieceLocation::Board(start + 3),
+        },
+        GameChange::CurrentPlayerChanged(current.current_player),
+      ]
+    );
+  }
+
+  #[test]
+  fn changed_players_need_full_update() {
+    let previous = get_game();
+    let mut current = previous.clone();
+    current.players.pop();
+    assert!(GameDelta::between(&previous, &current).is_none());
+  }
+}
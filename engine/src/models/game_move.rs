@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+use super::piece::PieceLocation;
+
+/// a single legal move of a piece, see `Game::legal_moves`
+/// board positions index the main board, home positions are offsets in the home column
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+  Promote {
+    piece_id: usize,
+    to: usize,
+    captures: bool,
+  },
+  Advance {
+    piece_id: usize,
+    from: usize,
+    to: usize,
+    captures: bool,
+  },
+  EnterHome {
+    piece_id: usize,
+    from: usize,
+    to: usize,
+  },
+  Finish {
+    piece_id: usize,
+    from: PieceLocation,
+  },
+  HomeAdvance {
+    piece_id: usize,
+    from: usize,
+    to: usize,
+  },
+}
+
+/// legal moves for one of the thrown dice, used when the dice are split
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DieMoves {
+  pub die_index: usize, // index in `Game::dice_throws`
+  pub value: usize,
+  pub moves: Vec<Move>,
+}
+
+impl Move {
+  pub fn piece_id(&self) -> usize {
+    match *self {
+      Move::Promote { piece_id, .. }
+      | Move::Advance { piece_id, .. }
+      | Move::EnterHome { piece_id, .. }
+      | Move::Finish { piece_id, .. }
+      | Move::HomeAdvance { piece_id, .. } => piece_id,
+    }
+  }
+
+  pub fn origin(&self) -> PieceLocation {
+    match *self {
+      Move::Promote { .. } => PieceLocation::Start,
+      Move::Advance { from, .. } | Move::EnterHome { from, .. } => PieceLocation::Board(from),
+      Move::Finish { from, .. } => from,
+      Move::HomeAdvance { from, .. } => PieceLocation::Home(from),
+    }
+  }
+
+  pub fn captures(&self) -> bool {
+    match *self {
+      Move::Promote { captures, .. } | Move::Advance { captures, .. } => captures,
+      _ => false,
+    }
+  }
+
+  pub fn destination(&self) -> PieceLocation {
+    match *self {
+      Move::Promote { to, .. } | Move::Advance { to, .. } => PieceLocation::Board(to),
+      Move::EnterHome { to, .. } | Move::HomeAdvance { to, .. } => PieceLocation::Home(to),
+      Move::Finish { .. } => PieceLocation::Finished,
+    }
+  }
+}
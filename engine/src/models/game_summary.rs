@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use super::{color::Color, game::Game};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum GameStatus {
+  Lobby,
+  InProgress,
+  Finished,
+}
+
+/// public part of a player, the player id is a secret and must not be exposed
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PlayerSummary {
+  pub name: String,
+  pub color: Color,
+  pub is_bot: bool,
+}
+
+/// what is shown about a game in the public listing
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GameSummary {
+  pub id: String,
+  pub status: GameStatus,
+  pub players: Vec<PlayerSummary>,
+  pub current_player: Color,
+  pub winner: Option<Color>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GameListPage {
+  pub games: Vec<GameSummary>,
+  pub page: u64,
+  pub per_page: u64,
+  pub total: u64,
+}
+
+impl GameStatus {
+  pub fn of(game: &Game) -> Self {
+    match (game.started, game.winner) {
+      (_, Some(_)) => GameStatus::Finished,
+      (true, None) => GameStatus::InProgress,
+      (false, None) => GameStatus::Lobby,
+    }
+  }
+}
+
+impl GameSummary {
+  pub fn new(id: String, game: &Game) -> Self {
+    GameSummary {
+      id,
+      status: GameStatus::of(game),
+      players: game
+        .players
+        .iter()
+        .map(|player| PlayerSummary {
+          name: player.name.clone(),
+          color: player.color,
+          is_bot: player.is_bot,
+        })
+        .collect(),
+      current_player: game.current_player,
+      winner: game.winner,
+    }
+  }
+}
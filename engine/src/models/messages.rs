@@ -0,0 +1,184 @@
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::{
+  chat::{ChatMessage, EmoteKind},
+  color::Color,
+  game::Game,
+  game_delta::GameDelta,
+  game_move::{DieMoves, Move},
+  ruleset::Ruleset,
+  server_error::ServerError,
+};
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ClientMessage {
+  ThrowDice,
+  MoveFigure(usize),               // piece id
+  MoveFigureWithDie(usize, usize), // piece id, index of the used die - only with split dice
+  PromotePiece,
+  StartGame,
+  Chat(String),
+  Emote(EmoteKind),
+  RequestSync,      // asks for the whole game after a missed GameDelta
+  RequestUndo,      // takes back own last move, the other players have to agree
+  AnswerUndo(bool), // whether the player agrees with the requested undo
+}
+
+impl ClientMessage {
+  /// name of the command without its fields, e.g. to label metrics and log events
+  pub fn name(&self) -> &'static str {
+    match self {
+      ClientMessage::ThrowDice => "ThrowDice",
+      ClientMessage::MoveFigure(_) => "MoveFigure",
+      ClientMessage::MoveFigureWithDie(_, _) => "MoveFigureWithDie",
+      ClientMessage::PromotePiece => "PromotePiece",
+      ClientMessage::StartGame => "StartGame",
+      ClientMessage::Chat(_) => "Chat",
+      ClientMessage::Emote(_) => "Emote",
+      ClientMessage::RequestSync => "RequestSync",
+      ClientMessage::RequestUndo => "RequestUndo",
+      ClientMessage::AnswerUndo(_) => "AnswerUndo",
+    }
+  }
+}
+
+/// commands can be wrapped with an id, errors caused by the command carry the same id
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum ClientRequest {
+  Tagged {
+    request_id: String,
+    message: ClientMessage,
+  },
+  Plain(ClientMessage),
+}
+
+impl ClientRequest {
+  pub fn into_parts(self) -> (ClientMessage, Option<String>) {
+    match self {
+      ClientRequest::Tagged {
+        request_id,
+        message,
+      } => (message, Some(request_id)),
+      ClientRequest::Plain(message) => (message, None),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "command", content = "payload")]
+pub enum ServerMessage {
+  DiceValue(usize, bool), // response to ThrowDice - bool: whether player should throw again
+  LegalMoves(Vec<Move>),  // moves the current player can make with the thrown dice
+  LegalMovesPerDie(Vec<DieMoves>), // sent instead of LegalMoves when the dice are split
+  SkipPlayer,
+  PiecePromoted, // response to PromotePiece
+  GameUpdate(#[serde(serialize_with = "serialize_public_game")] Game),
+  GameDelta(GameDelta),
+  PlayerCountChange(usize),
+  GameStarted(#[serde(serialize_with = "serialize_public_game")] Game),
+  Error(ServerError),
+  ConnectResponse(
+    #[serde(serialize_with = "serialize_public_game")] Game,
+    Color,
+    Vec<ChatMessage>, // recent chat history
+  ),
+  SpectateResponse(
+    #[serde(serialize_with = "serialize_public_game")] Game,
+    Vec<ChatMessage>,
+  ),
+  Chat(ChatMessage),
+  SpectatorCountChange(usize),
+  QueueUpdate(usize), // number of players waiting in the same matchmaking group
+  MatchFound {
+    game_id: String,
+    player_id: String,
+  },
+  UndoRequested(Color), // the player asks to take back the last move
+  UndoResolved(bool),   // whether the last move was taken back
+}
+
+impl ServerMessage {
+  /// name of the message without its payload, labels the sent messages in the metrics
+  pub fn name(&self) -> &'static str {
+    match self {
+      ServerMessage::DiceValue(_, _) => "DiceValue",
+      ServerMessage::LegalMoves(_) => "LegalMoves",
+      ServerMessage::LegalMovesPerDie(_) => "LegalMovesPerDie",
+      ServerMessage::SkipPlayer => "SkipPlayer",
+      ServerMessage::PiecePromoted => "PiecePromoted",
+      ServerMessage::GameUpdate(_) => "GameUpdate",
+      ServerMessage::GameDelta(_) => "GameDelta",
+      ServerMessage::PlayerCountChange(_) => "PlayerCountChange",
+      ServerMessage::GameStarted(_) => "GameStarted",
+      ServerMessage::Error(_) => "Error",
+      ServerMessage::ConnectResponse(_, _, _) => "ConnectResponse",
+      ServerMessage::SpectateResponse(_, _) => "SpectateResponse",
+      ServerMessage::Chat(_) => "Chat",
+      ServerMessage::SpectatorCountChange(_) => "SpectatorCountChange",
+      ServerMessage::QueueUpdate(_) => "QueueUpdate",
+      ServerMessage::MatchFound { .. } => "MatchFound",
+      ServerMessage::UndoRequested(_) => "UndoRequested",
+      ServerMessage::UndoResolved(_) => "UndoResolved",
+    }
+  }
+}
+
+/// player ids are the only credential of a seat, so they are never sent to clients,
+/// players find their own color in `ConnectResponse`.
+/// The move log grows with the game, it's left out as well and can be exported instead
+fn serialize_public_game<S: Serializer>(game: &Game, serializer: S) -> Result<S::Ok, S::Error> {
+  let mut game = game.clone();
+  for player in &mut game.players {
+    player.id.clear();
+  }
+  game.turns.clear();
+  game.serialize(serializer)
+}
+
+/// first message sent by a client after opening the matchmaking websocket
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct QueueRequest {
+  pub name: String,
+  pub player_count: usize,
+  #[serde(default)]
+  pub ruleset: Ruleset,
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::{player::Player, turn::Turn};
+
+  #[test]
+  fn games_are_sent_without_player_ids() {
+    let mut game = Game::new();
+    game.players = vec![
+      Player::new("secret-yellow".into(), "Alice".into(), Color::Yellow, false),
+      Player::new("secret-blue".into(), "Bob".into(), Color::Blue, false),
+    ];
+
+    for message in &[
+      ServerMessage::SpectateResponse(game.clone(), Vec::new()),
+      ServerMessage::ConnectResponse(game.clone(), Color::Yellow, Vec::new()),
+      ServerMessage::GameUpdate(game.clone()),
+      ServerMessage::GameStarted(game.clone()),
+    ] {
+      let json = serde_json::to_string(message).unwrap();
+      assert!(!json.contains("secret"), "{}", json);
+      assert!(json.contains("Alice"));
+    }
+    // the stored game keeps them
+    assert_eq!(game.players[0].id, "secret-yellow");
+  }
+
+  #[test]
+  fn games_are_sent_without_move_log() {
+    let mut game = Game::new();
+    game.turns = vec![Turn::new(Color::Yellow, vec![6, 2]); 100];
+
+    let json = serde_json::to_string(&ServerMessage::GameUpdate(game.clone())).unwrap();
+    assert!(json.contains("\"turns\":[]"), "{}", json);
+    assert_eq!(game.turns.len(), 100);
+  }
+}
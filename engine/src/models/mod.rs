@@ -0,0 +1,14 @@
+pub mod chat;
+pub mod color;
+pub mod game;
+pub mod game_delta;
+pub mod game_move;
+pub mod game_summary;
+pub mod messages;
+pub mod piece;
+pub mod player;
+pub mod position;
+pub mod ruleset;
+pub mod server_error;
+pub mod turn;
+pub mod visibility;
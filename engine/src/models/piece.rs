@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use super::color::Color;
+use super::game::{Game, BOARD_SIZE};
+use super::player::HOME_SIZE;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PieceLocation {
+  Start,
+  Board(usize),
+  Home(usize), // offset in the player's home column
+  Finished,
+}
+
+impl PieceLocation {
+  /// how many fields a piece has walked from the start to get here,
+  /// `starting_position` is where pieces of its color enter the board
+  pub fn progress(self, starting_position: usize) -> usize {
+    match self {
+      PieceLocation::Start => 0,
+      PieceLocation::Board(position) => {
+        (position + BOARD_SIZE - starting_position) % BOARD_SIZE + 1
+      }
+      PieceLocation::Home(offset) => BOARD_SIZE + offset,
+      PieceLocation::Finished => BOARD_SIZE + HOME_SIZE,
+    }
+  }
+
+  fn from_progress(progress: usize, starting_position: usize) -> Self {
+    match progress {
+      0 => PieceLocation::Start,
+      progress if progress < BOARD_SIZE => {
+        PieceLocation::Board((starting_position + progress - 1) % BOARD_SIZE)
+      }
+      progress if progress < BOARD_SIZE + HOME_SIZE => PieceLocation::Home(progress - BOARD_SIZE),
+      _ => PieceLocation::Finished,
+    }
+  }
+}
+
+/// a single pawn, the board and home columns are derived from the pieces of all players
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Piece {
+  /// unique within a game, see `Player::new`
+  pub id: usize,
+  pub color: Color,
+  pub location: PieceLocation,
+}
+
+impl Piece {
+  pub fn new(id: usize, color: Color) -> Self {
+    Piece {
+      id,
+      color,
+      location: PieceLocation::Start,
+    }
+  }
+
+  /// every location the piece passes on its way to `to`, both ends included,
+  /// a captured piece jumps back to start directly
+  pub fn path_to(&self, to: PieceLocation) -> Vec<PieceLocation> {
+    let starting_position = Game::offset_of(self.color) + 8;
+    let from_progress = self.location.progress(starting_position);
+    let to_progress = to.progress(starting_position);
+    if to_progress <= from_progress {
+      return vec![self.location, to];
+    }
+    (from_progress..=to_progress)
+      .map(|progress| PieceLocation::from_progress(progress, starting_position))
+      .collect()
+  }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::types::Field;
+
+use super::color::Color;
+use super::piece::{Piece, PieceLocation};
+
+pub const PIECES_COUNT: usize = 4;
+pub const HOME_SIZE: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Player {
+  pub id: String,
+  pub name: String,
+  pub color: Color,
+  pub pieces: Vec<Piece>,
+  pub is_bot: bool,
+}
+
+impl Player {
+  /// piece ids follow the order of colors, so they are unique within a game
+  pub fn new(id: String, name: String, color: Color, is_bot: bool) -> Self {
+    let first_id = Color::ordered()
+      .iter()
+      .position(|ordered| *ordered == color)
+      .unwrap()
+      * PIECES_COUNT;
+    Player {
+      id,
+      name,
+      color,
+      pieces: (first_id..first_id + PIECES_COUNT)
+        .map(|piece_id| Piece::new(piece_id, color))
+        .collect(),
+      is_bot,
+    }
+  }
+
+  fn count_pieces(&self, location: PieceLocation) -> usize {
+    self
+      .pieces
+      .iter()
+      .filter(|piece| piece.location == location)
+      .count()
+  }
+
+  pub fn pawns_at_start(&self) -> usize {
+    self.count_pieces(PieceLocation::Start)
+  }
+
+  pub fn pawns_at_finish(&self) -> usize {
+    self.count_pieces(PieceLocation::Finished)
+  }
+
+  pub fn piece_in_home(&self, offset: usize) -> Option<&Piece> {
+    self
+      .pieces
+      .iter()
+      .find(|piece| piece.location == PieceLocation::Home(offset))
+  }
+
+  /// player's home column (safe zone), derived from the pieces
+  pub fn home(&self) -> Vec<Field> {
+    let mut home = vec![None; HOME_SIZE];
+    for piece in &self.pieces {
+      if let PieceLocation::Home(offset) = piece.location {
+        home[offset] = Some(self.color);
+      }
+    }
+    home
+  }
+
+  pub fn get_piece_mut(&mut self, location: PieceLocation) -> Option<&mut Piece> {
+    self
+      .pieces
+      .iter_mut()
+      .find(|piece| piece.location == location)
+  }
+
+  /// returns whether all player's pieces are in home (occupy fields of home)
+  /// we assume there are 4 pieces for each player
+  pub fn check_winner(&self) -> bool {
+    self.pawns_at_finish() == PIECES_COUNT
+  }
+}
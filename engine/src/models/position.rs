@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use std::iter;
+
+use crate::utils::enums::PositionError;
+
+use super::color::Color;
+use super::game::BOARD_SIZE;
+use super::piece::PieceLocation;
+use super::player::{HOME_SIZE, PIECES_COUNT};
+use super::ruleset::Ruleset;
+
+/// pieces of one color, the pieces which aren't listed stay at start
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ColorPosition {
+  /// fields of the main board, see `PieceLocation::Board`
+  #[serde(default)]
+  pub board: Vec<usize>,
+  /// offsets in the home column
+  #[serde(default)]
+  pub home: Vec<usize>,
+  #[serde(default)]
+  pub finished: usize,
+}
+
+/// board state a game starts from instead of the initial one, used for puzzles and bug reports
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Position {
+  pub current_player: Color,
+  #[serde(default)]
+  pub green: ColorPosition,
+  #[serde(default)]
+  pub yellow: ColorPosition,
+  #[serde(default)]
+  pub blue: ColorPosition,
+  #[serde(default)]
+  pub red: ColorPosition,
+}
+
+impl Default for Position {
+  fn default() -> Self {
+    Position {
+      current_player: Color::ordered().first().unwrap().to_owned(),
+      green: ColorPosition::default(),
+      yellow: ColorPosition::default(),
+      blue: ColorPosition::default(),
+      red: ColorPosition::default(),
+    }
+  }
+}
+
+impl ColorPosition {
+  pub fn at_start(&self) -> usize {
+    PIECES_COUNT.saturating_sub(self.board.len() + self.home.len() + self.finished)
+  }
+
+  /// locations of the listed pieces, board first, then home and finish
+  pub fn locations(&self) -> Vec<PieceLocation> {
+    self
+      .board
+      .iter()
+      .map(|field| PieceLocation::Board(*field))
+      .chain(self.home.iter().map(|offset| PieceLocation::Home(*offset)))
+      .chain(iter::repeat_n(PieceLocation::Finished, self.finished))
+      .collect()
+  }
+
+  pub fn is_finished(&self) -> bool {
+    self.finished == PIECES_COUNT
+  }
+}
+
+impl Position {
+  pub fn get(&self, color: Color) -> &ColorPosition {
+    match color {
+      Color::Green => &self.green,
+      Color::Yellow => &self.yellow,
+      Color::Blue => &self.blue,
+      Color::Red => &self.red,
+    }
+  }
+
+  pub fn get_mut(&mut self, color: Color) -> &mut ColorPosition {
+    match color {
+      Color::Green => &mut self.green,
+      Color::Yellow => &mut self.yellow,
+      Color::Blue => &mut self.blue,
+      Color::Red => &mut self.red,
+    }
+  }
+
+  /// colors with all pieces at finish in the order of play, they are placed right away
+  pub fn placements(&self) -> Vec<Color> {
+    Color::ordered()
+      .into_iter()
+      .filter(|color| self.get(*color).is_finished())
+      .collect()
+  }
+
+  /// checks the position could occur in a game played with `ruleset`
+  pub fn validate(&self, ruleset: &Ruleset) -> Result<(), PositionError> {
+    let mut occupied = Vec::new();
+    for color in Color::ordered() {
+      let pieces = self.get(color);
+      if pieces.board.len() + pieces.home.len() + pieces.finished > PIECES_COUNT {
+        return Err(PositionError::TooManyPieces(color));
+      }
+      for &field in &pieces.board {
+        if field >= BOARD_SIZE {
+          return Err(PositionError::NoSuchField(field));
+        }
+        if occupied.contains(&field) {
+          return Err(PositionError::FieldTaken(field));
+        }
+        occupied.push(field);
+      }
+      for (index, &offset) in pieces.home.iter().enumerate() {
+        if offset >= HOME_SIZE {
+          return Err(PositionError::NoSuchHomeField(color, offset));
+        }
+        if pieces.home[..index].contains(&offset) {
+          return Err(PositionError::HomeFieldTaken(color, offset));
+        }
+      }
+    }
+
+    let placements = self.placements();
+    // the last remaining player is placed too, so the game would be over
+    if (!ruleset.full_ranking && !placements.is_empty())
+      || placements.len() + 1 >= Color::ordered().len()
+    {
+      return Err(PositionError::GameOver);
+    }
+    if placements.contains(&self.current_player) {
+      return Err(PositionError::CurrentPlayerFinished);
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::game::Game;
+  use crate::models::player::Player;
+
+  fn seated_game() -> Game {
+    let mut game = Game::new();
+    game.players = Color::ordered()
+      .into_iter()
+      .map(|color| Player::new("".into(), "".into(), color, false))
+      .collect();
+    game
+  }
+
+  #[test]
+  fn sets_up_the_pieces() {
+    let mut position = Position {
+      current_player: Color::Blue,
+      ..Position::default()
+    };
+    position.blue = ColorPosition {
+      board: vec![12],
+      home: vec![3],
+      finished: 1,
+    };
+    let mut game = seated_game();
+
+    game.set_up(&position);
+
+    let locations = game
+      .get_player(Color::Blue)
+      .pieces
+      .iter()
+      .map(|piece| piece.location)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      locations,
+      vec![
+        PieceLocation::Board(12),
+        PieceLocation::Home(3),
+        PieceLocation::Finished,
+        PieceLocation::Start
+      ]
+    );
+    assert_eq!(game.current_player, Color::Blue);
+    assert_eq!(game.get_player(Color::Red).pawns_at_start(), 4);
+  }
+
+  #[test]
+  fn rejects_shared_fields() {
+    let mut position = Position::default();
+    position.green.board = vec![5];
+    position.red.board = vec![5];
+
+    assert_eq!(
+      position.validate(&Ruleset::default()),
+      Err(PositionError::FieldTaken(5))
+    );
+  }
+
+  #[test]
+  fn finished_colors_need_full_ranking() {
+    let mut position = Position::default();
+    position.yellow.finished = 4;
+    let full_ranking = Ruleset {
+      full_ranking: true,
+      ..Ruleset::default()
+    };
+
+    assert_eq!(
+      position.validate(&Ruleset::default()),
+      Err(PositionError::GameOver)
+    );
+    assert_eq!(position.validate(&full_ranking), Ok(()));
+  }
+}
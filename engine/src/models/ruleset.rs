@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// optional rules chosen when the game is created
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Ruleset {
+  /// keep playing after the first winner until the whole finishing order is known
+  #[serde(default)]
+  pub full_ranking: bool,
+  /// every die of a bonus throw is used for a separate move instead of moving by their sum,
+  /// a single 6 then brings a piece to the starting position
+  #[serde(default)]
+  pub split_dice: bool,
+}
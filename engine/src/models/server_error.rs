@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::utils::enums::IllegalMoveReason;
+
+/// machine readable reason of ServerMessage::Error, clients can localize it
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize, Clone)]
+pub enum ErrorCode {
+  NotYourTurn,
+  WrongPhase,
+  IllegalMove { reason: IllegalMoveReason },
+  GameNotFound,
+  PlayerNotFound,
+  StorageFailure,
+  SpectatorNotAllowed,
+  InvalidRequest,
+  MessageEmpty,
+  MessageTooLong { max_length: usize },
+  RateLimited,
+  MalformedMessage,
+  UnknownCommand,
+  MessageTooLarge { max_size: usize },
+  UnsupportedFrame,
+  NothingToUndo,
+  TooManySpectators { max_spectators: usize },
+  GameAlreadyStarted,
+}
+
+impl ErrorCode {
+  /// name of the code without its fields, labels the error metrics
+  pub fn name(&self) -> &'static str {
+    match self {
+      ErrorCode::NotYourTurn => "NotYourTurn",
+      ErrorCode::WrongPhase => "WrongPhase",
+      ErrorCode::IllegalMove { .. } => "IllegalMove",
+      ErrorCode::GameNotFound => "GameNotFound",
+      ErrorCode::PlayerNotFound => "PlayerNotFound",
+      ErrorCode::StorageFailure => "StorageFailure",
+      ErrorCode::SpectatorNotAllowed => "SpectatorNotAllowed",
+      ErrorCode::InvalidRequest => "InvalidRequest",
+      ErrorCode::MessageEmpty => "MessageEmpty",
+      ErrorCode::MessageTooLong { .. } => "MessageTooLong",
+      ErrorCode::RateLimited => "RateLimited",
+      ErrorCode::MalformedMessage => "MalformedMessage",
+      ErrorCode::UnknownCommand => "UnknownCommand",
+      ErrorCode::MessageTooLarge { .. } => "MessageTooLarge",
+      ErrorCode::UnsupportedFrame => "UnsupportedFrame",
+      ErrorCode::NothingToUndo => "NothingToUndo",
+      ErrorCode::TooManySpectators { .. } => "TooManySpectators",
+      ErrorCode::GameAlreadyStarted => "GameAlreadyStarted",
+    }
+  }
+}
+
+impl fmt::Display for ErrorCode {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      ErrorCode::NotYourTurn => write!(f, "It is not your turn"),
+      ErrorCode::WrongPhase => write!(f, "This action is not allowed now"),
+      ErrorCode::IllegalMove { reason } => write!(f, "{}", reason),
+      ErrorCode::GameNotFound => write!(f, "Game not found"),
+      ErrorCode::PlayerNotFound => write!(f, "Player with given id not found"),
+      ErrorCode::StorageFailure => write!(f, "Server couldn't access the database"),
+      ErrorCode::SpectatorNotAllowed => write!(f, "Spectators can't play"),
+      ErrorCode::InvalidRequest => write!(f, "Invalid request"),
+      ErrorCode::MessageEmpty => write!(f, "Message is empty"),
+      ErrorCode::MessageTooLong { max_length } => {
+        write!(f, "Message is too long (max {} characters)", max_length)
+      }
+      ErrorCode::RateLimited => write!(f, "You are sending messages too fast"),
+      ErrorCode::MalformedMessage => write!(f, "Message couldn't be parsed"),
+      ErrorCode::UnknownCommand => write!(f, "Unknown command"),
+      ErrorCode::MessageTooLarge { max_size } => {
+        write!(f, "Message is too large (max {} bytes)", max_size)
+      }
+      ErrorCode::UnsupportedFrame => write!(f, "Only text messages are supported"),
+      ErrorCode::NothingToUndo => write!(f, "There is no move you could take back"),
+      ErrorCode::TooManySpectators { max_spectators } => {
+        write!(f, "The game already has {} spectators", max_spectators)
+      }
+      ErrorCode::GameAlreadyStarted => write!(f, "The game has already started"),
+    }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct ServerError {
+  pub code: ErrorCode,
+  pub message: String, // english description of the code, for clients which don't localize
+  pub request_id: Option<String>,
+}
+
+impl ServerError {
+  pub fn new(code: ErrorCode, request_id: Option<String>) -> Self {
+    ServerError {
+      message: code.to_string(),
+      code,
+      request_id,
+    }
+  }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use super::color::Color;
+use super::game_move::Move;
+
+/// a move made during a turn together with the color of the piece it sent back to start
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TurnMove {
+  pub piece_move: Move,
+  pub captured: Option<Color>,
+}
+
+/// one entry of the move log, a turn without moves was skipped
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Turn {
+  pub color: Color,
+  /// every throw of the turn, including the bonus throws after a 6
+  pub dice: Vec<usize>,
+  pub moves: Vec<TurnMove>,
+}
+
+impl Turn {
+  pub fn new(color: Color, dice: Vec<usize>) -> Self {
+    Turn {
+      color,
+      dice,
+      moves: Vec::new(),
+    }
+  }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+/// public games appear in the game listing, private ones can only be joined with an invite code
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Visibility {
+  #[default]
+  Public,
+  Private,
+}
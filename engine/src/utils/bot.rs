@@ -0,0 +1,114 @@
+use rand::Rng;
+
+use crate::models::game::Game;
+use crate::models::game_move::Move;
+use crate::models::piece::PieceLocation;
+
+pub fn create_bot_name() -> String {
+  let names = [
+    "Wade",
+    "Dave",
+    "Seth",
+    "Ivan",
+    "Riley",
+    "Gilbert",
+    "Jorge",
+    "Dan",
+    "Brian",
+    "Roberto",
+    "Ramon",
+    "Miles",
+    "Liam",
+    "Nathaniel",
+    "Ethan",
+    "Lewis",
+    "Milton",
+    "Claude",
+    "Joshua",
+    "Glen",
+  ];
+  let surnames = [
+    "Williams", "Harris", "Thomas", "Robinson", "Walker", "Scott", "Nelson", "Mitchell", "Morgan",
+    "Cooper", "Howard", "Davis", "Miller", "Martin", "Smith", "Anderson", "White", "Perry",
+    "Clark", "Richards",
+  ];
+  let mut rng = rand::thread_rng();
+  format!(
+    "{} {}",
+    names[rng.gen_range(0..names.len())],
+    surnames[rng.gen_range(0..surnames.len())]
+  )
+}
+
+/// picks one of the legal moves following steps 1. - 8. of the bot algorithm, see `move_bot`
+pub fn choose_bot_move(game: &Game, throw_sum: usize) -> Option<Move> {
+  let moves = game.legal_moves(throw_sum);
+  let player = game.get_current_player();
+  let is_on_board = |piece_move: &&Move| matches!(piece_move.origin(), PieceLocation::Board(_));
+  let is_promote = |piece_move: &&Move| matches!(piece_move, Move::Promote { .. });
+
+  // -----[ 1. jump to finish ]-----
+  let to_finish_from_board = moves
+    .iter()
+    .filter(|piece_move| matches!(piece_move, Move::Finish { .. }))
+    .filter(is_on_board)
+    .min_by_key(|piece_move| origin_position(piece_move));
+
+  // -----[ 2. jump to home ]-----
+  let to_home = moves
+    .iter()
+    .filter(|piece_move| matches!(piece_move, Move::EnterHome { .. }))
+    .min_by_key(|piece_move| origin_position(piece_move));
+
+  // -----[ 3. add new piece if only 1 is in main field ]-----
+  let early_promote = moves
+    .iter()
+    .filter(is_promote)
+    .find(|_| player.pawns_at_start() + player.pawns_at_finish() >= 3);
+
+  // -----[ 4. remove enemy's piece ]-----
+  let capture = moves
+    .iter()
+    .filter(|piece_move| matches!(piece_move, Move::Advance { captures: true, .. }))
+    .min_by_key(|piece_move| origin_position(piece_move));
+
+  // -----[ 5. add new piece to game ]-----
+  let promote = moves.iter().find(is_promote);
+
+  // -----[ 6. move any piece (on main field) ]-----
+  let advance = moves
+    .iter()
+    .filter(|piece_move| matches!(piece_move, Move::Advance { .. }))
+    .max_by_key(|piece_move| origin_position(piece_move));
+
+  // -----[ 7. jump from home to finish ]-----
+  let to_finish_from_home = moves
+    .iter()
+    .filter(|piece_move| matches!(piece_move, Move::Finish { .. }))
+    .filter(|piece_move| !is_on_board(piece_move))
+    .max_by_key(|piece_move| origin_position(piece_move));
+
+  // -----[ 8. move piece forward in home ]-----
+  let home_advance = moves
+    .iter()
+    .filter(|piece_move| matches!(piece_move, Move::HomeAdvance { .. }))
+    .max_by_key(|piece_move| origin_position(piece_move));
+
+  to_finish_from_board
+    .or(to_home)
+    .or(early_promote)
+    .or(capture)
+    .or(promote)
+    .or(advance)
+    .or(to_finish_from_home)
+    .or(home_advance)
+    .copied()
+}
+
+/// position of the moved piece on the board or in the home column
+fn origin_position(piece_move: &Move) -> usize {
+  match piece_move.origin() {
+    PieceLocation::Board(position) | PieceLocation::Home(position) => position,
+    _ => 0,
+  }
+}
@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::models::color::Color;
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize, Clone)]
+pub enum MoveResult {
+  Winner(Color),
+  Success(String),
+  Error(IllegalMoveReason),
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize, Clone, Copy)]
+pub enum IllegalMoveReason {
+  OverjumpsHome,
+  HomeFieldOccupied,
+  FieldOccupied,
+  CannotPromote,
+  NotYourPiece,
+  PieceFinished,
+  NoSuchDie,
+}
+
+impl fmt::Display for IllegalMoveReason {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let text = match self {
+      IllegalMoveReason::OverjumpsHome => "Can't move - would overjump home.",
+      IllegalMoveReason::HomeFieldOccupied => "Can't move - home field is already occupied.",
+      IllegalMoveReason::FieldOccupied => "Can't move - field is occupied by our piece.",
+      IllegalMoveReason::CannotPromote => "You can't promote a piece.",
+      IllegalMoveReason::NotYourPiece => "You can only move your own pieces.",
+      IllegalMoveReason::PieceFinished => "This piece has already finished.",
+      IllegalMoveReason::NoSuchDie => "There is no such die to move with.",
+    };
+    write!(f, "{}", text)
+  }
+}
+
+/// reasons why a game can't start from a `Position`
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize, Clone, Copy)]
+pub enum PositionError {
+  TooManyPieces(Color),
+  NoSuchField(usize),
+  FieldTaken(usize),
+  NoSuchHomeField(Color, usize),
+  HomeFieldTaken(Color, usize),
+  GameOver,
+  CurrentPlayerFinished,
+}
+
+impl fmt::Display for PositionError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      PositionError::TooManyPieces(color) => write!(f, "{:?} has more than 4 pieces.", color),
+      PositionError::NoSuchField(field) => write!(f, "There is no field {} on the board.", field),
+      PositionError::FieldTaken(field) => write!(f, "Field {} holds more than one piece.", field),
+      PositionError::NoSuchHomeField(color, offset) => {
+        write!(f, "{:?}'s home has no field {}.", color, offset)
+      }
+      PositionError::HomeFieldTaken(color, offset) => {
+        write!(
+          f,
+          "{:?}'s home field {} holds more than one piece.",
+          color, offset
+        )
+      }
+      PositionError::GameOver => write!(f, "The game would already be over."),
+      PositionError::CurrentPlayerFinished => {
+        write!(f, "The player on turn has already finished.")
+      }
+    }
+  }
+}
+
+pub enum MoveType {
+  Promote,
+  Move(usize),               // piece id
+  MoveWithDie(usize, usize), // piece id, die index
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize, Clone, Copy)]
+pub enum RoundPhase {
+  Rolling,
+  Moving,
+}
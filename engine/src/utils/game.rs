@@ -28,16 +28,20 @@ pub fn fill_with_bots(players: Vec<Player>) -> Vec<Player> {
   })
 }
 
-/// called upon receiving either PromotePiece or MovePiece(position, Option<Color>)
-pub async fn play_round(game: &mut Game, move_type: MoveType) -> MoveResult {
+/// called upon receiving PromotePiece, MoveFigure(piece_id) or MoveFigureWithDie(piece_id, die_index)
+pub fn play_round(game: &mut Game, move_type: MoveType) -> MoveResult {
   let mut move_result = make_a_move(game, move_type);
 
-  if let Some(winner) = game.check_winner() {
+  if let Some(winner) = game.update_placements() {
     move_result = MoveResult::Winner(winner);
     game.finish_game(winner);
   }
 
   if let MoveResult::Success(_) = move_result {
+    // with split dice the player keeps moving while some die can be used
+    if game.ruleset.split_dice && game.has_legal_moves() {
+      return move_result;
+    }
     game.update_current_player();
     game.dice_throws.clear();
   }
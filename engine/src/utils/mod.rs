@@ -0,0 +1,6 @@
+pub mod bot;
+pub mod dice;
+pub mod enums;
+pub mod game;
+pub mod notation;
+pub mod player;
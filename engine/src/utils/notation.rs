@@ -0,0 +1,565 @@
+//! text notation of a game, a header of tags followed by one line per turn:
+//!
+//! ```text
+//! [Ruleset "standard"]
+//! [Human "G Alice"]
+//! [Bot "Y Wade Williams"]
+//! [Bot "B Jane Smith"]
+//! [Bot "R John Brown"]
+//!
+//! G: 6,4 P+ 51
+//! Y: 6,1 P+ 9
+//! B: 3 -
+//! R: 2 -
+//! G: 5 m51->4
+//! Y: 2 m9->11
+//! B: 5 -
+//! R: 1 -
+//! G: 6,1 m4->11 x(Y)
+//! ```
+//!
+//! board fields are numbered like `PieceLocation::Board`, `h2` is a field of the home column
+//! and `F` the finish, a capture is noted behind the move that caused it
+//!
+//! games set up from a `Position` have a `[Position "B G:47,h2 Y:8,F,F B: R:"]` tag,
+//! the color on turn followed by the pieces which aren't at start
+
+use std::fmt;
+
+use crate::models::color::Color;
+use crate::models::game::Game;
+use crate::models::game_move::Move;
+use crate::models::piece::PieceLocation;
+use crate::models::player::Player;
+use crate::models::position::Position;
+use crate::models::ruleset::Ruleset;
+use crate::models::turn::Turn;
+use crate::utils::enums::{MoveResult, RoundPhase};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotationError {
+  /// 1-based line of the text
+  pub line: usize,
+  pub message: String,
+}
+
+impl fmt::Display for NotationError {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "line {}: {}", self.line, self.message)
+  }
+}
+
+fn color_letter(color: Color) -> char {
+  match color {
+    Color::Green => 'G',
+    Color::Yellow => 'Y',
+    Color::Blue => 'B',
+    Color::Red => 'R',
+  }
+}
+
+fn parse_color(letter: &str) -> Option<Color> {
+  match letter {
+    "G" => Some(Color::Green),
+    "Y" => Some(Color::Yellow),
+    "B" => Some(Color::Blue),
+    "R" => Some(Color::Red),
+    _ => None,
+  }
+}
+
+fn location_to_string(location: PieceLocation) -> String {
+  match location {
+    PieceLocation::Start => String::from("S"),
+    PieceLocation::Board(position) => position.to_string(),
+    PieceLocation::Home(offset) => format!("h{}", offset),
+    PieceLocation::Finished => String::from("F"),
+  }
+}
+
+fn parse_location(text: &str) -> Option<PieceLocation> {
+  match text {
+    "S" => Some(PieceLocation::Start),
+    "F" => Some(PieceLocation::Finished),
+    _ => match text.strip_prefix('h') {
+      Some(offset) => offset.parse().ok().map(PieceLocation::Home),
+      None => text.parse().ok().map(PieceLocation::Board),
+    },
+  }
+}
+
+fn move_to_string(piece_move: &Move) -> String {
+  match piece_move {
+    Move::Promote { to, .. } => format!("P+ {}", to),
+    _ => format!(
+      "m{}->{}",
+      location_to_string(piece_move.origin()),
+      location_to_string(piece_move.destination())
+    ),
+  }
+}
+
+impl fmt::Display for Turn {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let dice = self
+      .dice
+      .iter()
+      .map(|die| die.to_string())
+      .collect::<Vec<_>>()
+      .join(",");
+    write!(f, "{}: {}", color_letter(self.color), dice)?;
+    if self.moves.is_empty() {
+      return write!(f, " -");
+    }
+    for turn_move in &self.moves {
+      write!(f, " {}", move_to_string(&turn_move.piece_move))?;
+      if let Some(captured) = turn_move.captured {
+        write!(f, " x({})", color_letter(captured))?;
+      }
+    }
+    Ok(())
+  }
+}
+
+fn position_to_string(position: &Position) -> String {
+  let mut parts = vec![color_letter(position.current_player).to_string()];
+  for color in Color::ordered() {
+    let locations = position
+      .get(color)
+      .locations()
+      .into_iter()
+      .map(location_to_string)
+      .collect::<Vec<_>>();
+    parts.push(format!("{}:{}", color_letter(color), locations.join(",")));
+  }
+  parts.join(" ")
+}
+
+fn parse_position(value: &str) -> Result<Position, String> {
+  let mut parts = value.split_whitespace();
+  let current_player = parts
+    .next()
+    .and_then(parse_color)
+    .ok_or_else(|| String::from("the position starts with the color on turn"))?;
+  let mut position = Position {
+    current_player,
+    ..Position::default()
+  };
+  for part in parts {
+    let (letter, locations) = part
+      .split_once(':')
+      .ok_or_else(|| format!("invalid pieces {}", part))?;
+    let color = parse_color(letter).ok_or_else(|| format!("unknown color {}", letter))?;
+    let pieces = position.get_mut(color);
+    for location in locations.split(',').filter(|location| !location.is_empty()) {
+      match parse_location(location) {
+        Some(PieceLocation::Board(field)) => pieces.board.push(field),
+        Some(PieceLocation::Home(offset)) => pieces.home.push(offset),
+        Some(PieceLocation::Finished) => pieces.finished += 1,
+        _ => return Err(format!("invalid location {}", location)),
+      }
+    }
+  }
+  Ok(position)
+}
+
+/// a move as written in the notation, matched against the legal moves while replaying
+struct NotedMove {
+  from: PieceLocation,
+  to: PieceLocation,
+  captured: Option<Color>,
+}
+
+/// parses `[Key "value"]`
+fn parse_tag(line: &str) -> Option<(&str, &str)> {
+  let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+  let (key, value) = inner.split_once(' ')?;
+  let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+  Some((key, value))
+}
+
+fn parse_ruleset(value: &str) -> Result<Ruleset, String> {
+  let mut ruleset = Ruleset::default();
+  for rule in value.split_whitespace() {
+    match rule {
+      "standard" => {}
+      "full_ranking" => ruleset.full_ranking = true,
+      "split_dice" => ruleset.split_dice = true,
+      _ => return Err(format!("unknown rule {}", rule)),
+    }
+  }
+  Ok(ruleset)
+}
+
+fn parse_seat(value: &str, is_bot: bool) -> Result<Player, String> {
+  let (letter, name) = value.split_once(' ').unwrap_or((value, ""));
+  let color = parse_color(letter).ok_or_else(|| format!("unknown color {}", letter))?;
+  Ok(Player::new(
+    String::from("0"),
+    name.trim().to_string(),
+    color,
+    is_bot,
+  ))
+}
+
+fn parse_dice(text: &str) -> Result<Vec<usize>, String> {
+  text
+    .split(',')
+    .map(|die| match die.parse() {
+      Ok(value) if (1..=6).contains(&value) => Ok(value),
+      _ => Err(format!("invalid die {}", die)),
+    })
+    .collect()
+}
+
+fn parse_moves(tokens: &[&str]) -> Result<Vec<NotedMove>, String> {
+  let mut moves: Vec<NotedMove> = Vec::new();
+  let mut index = 0;
+  while index < tokens.len() {
+    let token = tokens[index];
+    if token == "-" {
+      if tokens.len() > 1 {
+        return Err(String::from("a skipped turn can't have any moves"));
+      }
+    } else if token == "P+" {
+      index += 1;
+      let to = tokens
+        .get(index)
+        .and_then(|to| to.parse().ok())
+        .ok_or_else(|| String::from("promotion without a field"))?;
+      moves.push(NotedMove {
+        from: PieceLocation::Start,
+        to: PieceLocation::Board(to),
+        captured: None,
+      });
+    } else if let Some(captured) = token.strip_prefix("x(").and_then(|c| c.strip_suffix(')')) {
+      let color = parse_color(captured).ok_or_else(|| format!("unknown color {}", captured))?;
+      match moves.last_mut() {
+        Some(noted) if noted.captured.is_none() => noted.captured = Some(color),
+        _ => return Err(format!("capture {} doesn't follow a move", token)),
+      }
+    } else {
+      let (from, to) = token
+        .strip_prefix('m')
+        .and_then(|fields| fields.split_once("->"))
+        .ok_or_else(|| format!("unknown move {}", token))?;
+      match (parse_location(from), parse_location(to)) {
+        (Some(from), Some(to)) => moves.push(NotedMove {
+          from,
+          to,
+          captured: None,
+        }),
+        _ => return Err(format!("unknown move {}", token)),
+      }
+    }
+    index += 1;
+  }
+  Ok(moves)
+}
+
+impl Game {
+  /// writes the header and the move log, the open turn of the current player comes last
+  pub fn to_notation(&self) -> String {
+    let mut lines = Vec::new();
+    let mut rules = Vec::new();
+    if self.ruleset.full_ranking {
+      rules.push("full_ranking");
+    }
+    if self.ruleset.split_dice {
+      rules.push("split_dice");
+    }
+    if rules.is_empty() {
+      rules.push("standard");
+    }
+    lines.push(format!("[Ruleset \"{}\"]", rules.join(" ")));
+    for player in &self.players {
+      let tag = if player.is_bot { "Bot" } else { "Human" };
+      lines.push(format!(
+        "[{} \"{} {}\"]",
+        tag,
+        color_letter(player.color),
+        player.name
+      ));
+    }
+    if let Some(seed) = self.seed {
+      lines.push(format!("[Seed \"{}\"]", seed));
+    }
+    if let Some(position) = &self.position {
+      lines.push(format!("[Position \"{}\"]", position_to_string(position)));
+    }
+    lines.push(String::new());
+    for turn in self.turns.iter().chain(self.current_turn.iter()) {
+      lines.push(turn.to_string());
+    }
+    lines.join("\n") + "\n"
+  }
+
+  /// replays a game written by `to_notation`, every turn has to follow the rules
+  pub fn from_notation(text: &str) -> Result<Game, NotationError> {
+    let mut game = Game::new();
+    let lines = text
+      .lines()
+      .enumerate()
+      .map(|(index, line)| (index + 1, line.trim()))
+      .filter(|(_, line)| !line.is_empty())
+      .collect::<Vec<_>>();
+
+    let mut turns_started = false;
+    for (position, &(line, content)) in lines.iter().enumerate() {
+      let error = |message: String| NotationError { line, message };
+      if content.starts_with('[') {
+        if turns_started {
+          return Err(error(String::from("tags have to precede the turns")));
+        }
+        let (key, value) = parse_tag(content).ok_or_else(|| error(String::from("invalid tag")))?;
+        match key {
+          "Ruleset" => game.ruleset = parse_ruleset(value).map_err(error)?,
+          "Human" | "Bot" => {
+            let player = parse_seat(value, key == "Bot").map_err(error)?;
+            if game.players.iter().any(|seat| seat.color == player.color) {
+              return Err(error(format!("{:?} is seated twice", player.color)));
+            }
+            game.players.push(player);
+          }
+          "Position" => game.position = Some(parse_position(value).map_err(error)?),
+          "Seed" => {
+            let seed = value
+              .parse()
+              .map_err(|_| error(format!("invalid seed {}", value)))?;
+            game.seed = Some(seed);
+          }
+          _ => return Err(error(format!("unknown tag {}", key))),
+        }
+        continue;
+      }
+
+      if !turns_started {
+        if game.players.len() != Color::ordered().len() {
+          return Err(error(String::from("every color needs a seat")));
+        }
+        if let Some(position) = game.position.clone() {
+          position
+            .validate(&game.ruleset)
+            .map_err(|reason| error(reason.to_string()))?;
+          game.set_up(&position);
+        }
+        game.started = true;
+        turns_started = true;
+      }
+      let is_last = position + 1 == lines.len();
+      replay_turn(&mut game, content, is_last).map_err(error)?;
+    }
+    Ok(game)
+  }
+}
+
+/// plays one line of the notation, only the last turn may stay unfinished
+fn replay_turn(game: &mut Game, content: &str, is_last: bool) -> Result<(), String> {
+  if game.winner.is_some() {
+    return Err(String::from("the game is already over"));
+  }
+  let (letter, rest) = content
+    .split_once(':')
+    .ok_or_else(|| String::from("a turn starts with the color"))?;
+  let color = parse_color(letter.trim()).ok_or_else(|| format!("unknown color {}", letter))?;
+  if color != game.current_player {
+    return Err(format!("it's {:?}'s turn", game.current_player));
+  }
+  let tokens = rest.split_whitespace().collect::<Vec<_>>();
+  let (dice, tokens) = tokens
+    .split_first()
+    .ok_or_else(|| String::from("a turn needs the dice"))?;
+  game.dice_throws = parse_dice(dice)?;
+  game.round_phase = RoundPhase::Moving;
+  let moves = parse_moves(tokens)?;
+
+  if !game.ruleset.split_dice && moves.len() > 1 {
+    return Err(String::from("only one move is allowed"));
+  }
+  if moves.is_empty() && game.dice_throws.iter().sum::<usize>() != 18 && game.has_legal_moves() {
+    return Err(String::from("a legal move was skipped"));
+  }
+
+  for noted in &moves {
+    if game.winner.is_some() {
+      return Err(String::from("the game is already over"));
+    }
+    let dice = match game.ruleset.split_dice {
+      true => game
+        .dice_throws
+        .iter()
+        .copied()
+        .enumerate()
+        .collect::<Vec<_>>(),
+      false => vec![(0, game.dice_throws.iter().sum())],
+    };
+    let (die_index, value, piece_move) = dice
+      .into_iter()
+      .find_map(|(die_index, value)| {
+        game
+          .legal_moves(value)
+          .into_iter()
+          .find(|piece_move| {
+            piece_move.origin() == noted.from && piece_move.destination() == noted.to
+          })
+          .map(|piece_move| (die_index, value, piece_move))
+      })
+      .ok_or_else(|| {
+        format!(
+          "{:?} can't move from {} to {}",
+          color,
+          location_to_string(noted.from),
+          location_to_string(noted.to)
+        )
+      })?;
+
+    if let MoveResult::Error(reason) = game.execute_move(piece_move, value) {
+      return Err(reason.to_string());
+    }
+    if game.ruleset.split_dice {
+      game.dice_throws.remove(die_index);
+    }
+    let captured = game
+      .current_turn
+      .as_ref()
+      .and_then(|turn| turn.moves.last())
+      .and_then(|turn_move| turn_move.captured);
+    if captured != noted.captured {
+      return Err(format!(
+        "the capture doesn't match, the move captures {:?}",
+        captured
+      ));
+    }
+    if let Some(winner) = game.update_placements() {
+      game.finish_game(winner);
+    }
+  }
+
+  if game.winner.is_some() {
+    return Ok(());
+  }
+  if game.ruleset.split_dice && !moves.is_empty() && game.has_legal_moves() {
+    if is_last {
+      return Ok(());
+    }
+    return Err(String::from(
+      "the turn isn't finished, some dice can still be used",
+    ));
+  }
+  game.update_current_player();
+  game.dice_throws.clear();
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::game::BOARD_SIZE;
+  use crate::utils::enums::MoveType;
+  use crate::utils::game::play_round;
+
+  const HEADER: &str = "[Ruleset \"standard\"]
+[Human \"G Alice\"]
+[Bot \"Y Wade Williams\"]
+[Bot \"B Jane Smith\"]
+[Bot \"R John Brown\"]
+";
+
+  fn play(game: &mut Game, dice: Vec<usize>, move_type: Option<MoveType>) {
+    game.dice_throws = dice;
+    game.round_phase = RoundPhase::Moving;
+    match move_type {
+      Some(move_type) => {
+        play_round(game, move_type);
+      }
+      None => {
+        game.update_current_player();
+        game.dice_throws.clear();
+      }
+    }
+  }
+
+  fn first_piece(game: &Game, color: Color) -> usize {
+    game.get_player(color).pieces[0].id
+  }
+
+  #[test]
+  fn replays_its_own_notation() {
+    let mut game = Game::from_notation(HEADER).unwrap();
+    let green = first_piece(&game, Color::Green);
+    play(&mut game, vec![6, 4], Some(MoveType::Promote));
+    play(&mut game, vec![3], None);
+    play(&mut game, vec![2], None);
+    play(&mut game, vec![1], None);
+    play(&mut game, vec![5], Some(MoveType::Move(green)));
+    let text = game.to_notation();
+
+    let replayed = Game::from_notation(&text).unwrap();
+
+    assert_eq!(replayed.turns, game.turns);
+    assert_eq!(replayed.current_player, Color::Yellow);
+    assert_eq!(replayed.to_notation(), text);
+  }
+
+  #[test]
+  fn notes_captures() {
+    let mut game = Game::from_notation(HEADER).unwrap();
+    let green = first_piece(&game, Color::Green);
+    play(&mut game, vec![6, 4], Some(MoveType::Promote));
+    let from = match game.get_piece(green).unwrap().location {
+      PieceLocation::Board(position) => position,
+      location => panic!("promoted to {:?}", location),
+    };
+    let to = (from + 3) % BOARD_SIZE;
+    let yellow = first_piece(&game, Color::Yellow);
+    game.get_player_mut(Color::Yellow).pieces[0].location = PieceLocation::Board(to);
+    game.current_player = Color::Green;
+    play(&mut game, vec![3], Some(MoveType::Move(green)));
+
+    assert_eq!(
+      game.get_piece(yellow).unwrap().location,
+      PieceLocation::Start
+    );
+    assert!(game
+      .to_notation()
+      .ends_with(&format!("G: 3 m{}->{} x(Y)\n", from, to)));
+  }
+
+  #[test]
+  fn keeps_the_seed() {
+    let game = Game::from_notation(&format!("{}[Seed \"42\"]\n", HEADER)).unwrap();
+
+    assert_eq!(game.seed, Some(42));
+    assert!(game.to_notation().contains("[Seed \"42\"]"));
+  }
+
+  #[test]
+  fn replays_from_a_position() {
+    let text = format!(
+      "{}[Position \"Y G:47,h2 Y:8,F,F B: R:\"]\n\nY: 2 m8->10\n",
+      HEADER
+    );
+    let game = Game::from_notation(&text).unwrap();
+
+    assert_eq!(
+      game.get_player(Color::Yellow).pieces[0].location,
+      PieceLocation::Board(10)
+    );
+    assert_eq!(game.current_player, Color::Blue);
+    assert_eq!(game.to_notation(), text);
+  }
+
+  #[test]
+  fn rejects_illegal_moves() {
+    let error = Game::from_notation(&format!("{}\nG: 5 m0->5\n", HEADER)).unwrap_err();
+
+    assert_eq!(error.line, 7);
+  }
+
+  #[test]
+  fn rejects_skipped_moves() {
+    let error = Game::from_notation(&format!("{}\nG: 6,4 -\n", HEADER)).unwrap_err();
+
+    assert_eq!(error.message, "a legal move was skipped");
+  }
+}
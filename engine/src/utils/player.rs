@@ -0,0 +1,47 @@
+use crate::models::game::Game;
+use crate::models::piece::PieceLocation;
+use crate::utils::enums::{IllegalMoveReason, MoveResult};
+
+use super::enums::MoveType;
+
+pub fn make_a_move(game: &mut Game, player_move: MoveType) -> MoveResult {
+  if game.ruleset.split_dice {
+    return make_a_move_with_die(game, player_move);
+  }
+
+  let dice_value = game.dice_throws.iter().sum();
+  match player_move {
+    MoveType::Promote => game.promote_piece(dice_value),
+    MoveType::Move(piece_id) => game.move_piece(piece_id, dice_value),
+    // there is just the sum of the dice
+    MoveType::MoveWithDie(_, _) => MoveResult::Error(IllegalMoveReason::NoSuchDie),
+  }
+}
+
+/// moves without a die given use the first die the piece can move with
+fn make_a_move_with_die(game: &mut Game, player_move: MoveType) -> MoveResult {
+  let piece_id = match player_move {
+    MoveType::MoveWithDie(piece_id, die_index) => {
+      return game.move_piece_with_die(piece_id, die_index)
+    }
+    MoveType::Move(piece_id) => piece_id,
+    MoveType::Promote => match game
+      .get_current_player()
+      .pieces
+      .iter()
+      .find(|piece| piece.location == PieceLocation::Start)
+    {
+      Some(piece) => piece.id,
+      None => return MoveResult::Error(IllegalMoveReason::CannotPromote),
+    },
+  };
+
+  let mut move_result = MoveResult::Error(IllegalMoveReason::NoSuchDie);
+  for die_index in 0..game.dice_throws.len() {
+    move_result = game.move_piece_with_die(piece_id, die_index);
+    if let MoveResult::Success(_) = move_result {
+      break;
+    }
+  }
+  move_result
+}
@@ -0,0 +1,45 @@
+use std::{borrow::Cow, fs, path::PathBuf};
+
+use crate::models::server_config::ClientConfig;
+
+#[cfg(feature = "embed-client")]
+#[derive(rust_embed::RustEmbed)]
+#[folder = "../client/dist/"]
+struct EmbeddedClient;
+
+/// where the files of the Trunk build of the client come from
+pub enum ClientBundle {
+  Directory(PathBuf),
+  #[cfg(feature = "embed-client")]
+  Embedded,
+}
+
+impl ClientBundle {
+  /// the bundle the config asks for, none if the client is hosted somewhere else
+  pub fn from_config(config: &ClientConfig) -> Option<Self> {
+    #[cfg(feature = "embed-client")]
+    if config.embedded {
+      return Some(ClientBundle::Embedded);
+    }
+    config
+      .static_dir
+      .as_ref()
+      .map(|static_dir| ClientBundle::Directory(PathBuf::from(static_dir)))
+  }
+
+  /// contents of the file at `path` relative to the root of the bundle
+  pub fn read(&self, path: &str) -> Option<Cow<'static, [u8]>> {
+    // the path comes from the request, it mustn't leave the bundle
+    if path
+      .split('/')
+      .any(|part| part == ".." || part.contains('\\'))
+    {
+      return None;
+    }
+    match self {
+      ClientBundle::Directory(root) => fs::read(root.join(path)).ok().map(Cow::Owned),
+      #[cfg(feature = "embed-client")]
+      ClientBundle::Embedded => EmbeddedClient::get(path).map(|file| file.data),
+    }
+  }
+}
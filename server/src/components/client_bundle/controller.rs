@@ -0,0 +1,39 @@
+use actix_web::{
+  http::{header, Method},
+  web, HttpRequest, HttpResponse,
+};
+
+use super::bundle::ClientBundle;
+
+const INDEX_FILE: &str = "index.html";
+
+/// serves the files of the client, paths which aren't files are routes of the client
+/// (e.g. `/games/{id}/lobby`), they get `index.html` and the client's router takes over
+pub async fn serve_client(req: HttpRequest, bundle: web::Data<ClientBundle>) -> HttpResponse {
+  if req.method() != Method::GET && req.method() != Method::HEAD {
+    return HttpResponse::NotFound().finish();
+  }
+  let path = req.path().trim_start_matches('/');
+  let is_page = path.is_empty() || path == INDEX_FILE;
+
+  if let Some(content) = bundle.read(path).filter(|_| !is_page) {
+    let content_type = mime_guess::from_path(path).first_or_octet_stream();
+    return HttpResponse::Ok()
+      .content_type(content_type.as_ref())
+      .body(content.into_owned());
+  }
+
+  // a missing asset, e.g. an optional config.json, shouldn't turn into the page
+  let is_file = path
+    .rsplit('/')
+    .next()
+    .is_some_and(|name| name.contains('.'));
+  match bundle.read(INDEX_FILE) {
+    Some(index) if is_page || !is_file => HttpResponse::Ok()
+      .content_type("text/html; charset=utf-8")
+      // the page refers to the assets by hash, it's the only file which changes between builds
+      .insert_header((header::CACHE_CONTROL, "no-cache"))
+      .body(index.into_owned()),
+    _ => HttpResponse::NotFound().body("File not found"),
+  }
+}
@@ -0,0 +1,12 @@
+use actix_web::web;
+
+use super::{bundle::ClientBundle, controller};
+
+/// everything the api doesn't handle goes to the client, if the server hosts it
+pub fn attach_routes(config: &mut web::ServiceConfig, bundle: &Option<web::Data<ClientBundle>>) {
+  if let Some(bundle) = bundle {
+    config
+      .app_data(bundle.clone())
+      .default_service(web::to(controller::serve_client));
+  }
+}
@@ -1,26 +1,182 @@
 use actix_web::{get, post, put, web, HttpRequest, HttpResponse};
 use actix_web_actors::ws;
-use futures::stream::TryStreamExt;
-use serde::Deserialize;
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
 use uuid::Uuid;
 
-use crate::models::{app_data::AppData, color::Color, game::Game, player::Player};
+use crate::models::{
+  app_data::AppData,
+  color::Color,
+  game::Game,
+  game_summary::GameStatus,
+  player::Player,
+  position::Position,
+  ruleset::Ruleset,
+  visibility::Visibility,
+  wire_format::{WireFormat, WireFormatQuery},
+};
+use crate::utils::invite_code::{create_invite_code, normalize_invite_code};
 
+use super::super::matchmaking::session::MatchmakingSession;
 use super::super::session::actor::GameSession;
-use super::database;
+use super::database::{self, DuplicateInviteCode};
+
+#[derive(Deserialize, Default)]
+pub struct CreateGameBody {
+  #[serde(default)]
+  pub ruleset: Ruleset,
+  #[serde(default)]
+  pub visibility: Visibility,
+  /// the game starts from this position instead of the initial one
+  #[serde(default)]
+  pub position: Option<Position>,
+}
+
+#[derive(Serialize)]
+pub struct CreateGameResponse {
+  pub id: String,
+  pub invite_code: Option<String>,
+}
+
+const INVITE_CODE_ATTEMPTS: usize = 10;
+
+/// stores the game, a private one gets an invite code which isn't used by any other game,
+/// the unique index rejects a code taken in the meantime and a new one is tried
+async fn insert_game(
+  data: &web::Data<AppData>,
+  mut game: Game,
+) -> anyhow::Result<CreateGameResponse> {
+  if game.visibility == Visibility::Public {
+    let id = database::create_game(&data.db, game).await?;
+    return Ok(CreateGameResponse {
+      id,
+      invite_code: None,
+    });
+  }
+  for _ in 0..INVITE_CODE_ATTEMPTS {
+    let invite_code = create_invite_code();
+    game.invite_code = Some(invite_code.clone());
+    match database::create_game(&data.db, game.clone()).await {
+      Ok(id) => {
+        return Ok(CreateGameResponse {
+          id,
+          invite_code: Some(invite_code),
+        })
+      }
+      Err(e) if e.is::<DuplicateInviteCode>() => {}
+      Err(e) => return Err(e),
+    }
+  }
+  Err(anyhow!("no unused invite code found"))
+}
 
 #[post("")]
-pub async fn create_new_game(data: web::Data<AppData>) -> HttpResponse {
-  let game_res = database::create_game(&data.db).await;
-  match game_res {
-    Ok(id) => HttpResponse::Ok().body(id),
+pub async fn create_new_game(
+  body: Option<web::Json<CreateGameBody>>,
+  data: web::Data<AppData>,
+) -> HttpResponse {
+  let body = body.map(|body| body.into_inner()).unwrap_or_default();
+
+  if let Some(position) = &body.position {
+    if let Err(reason) = position.validate(&body.ruleset) {
+      return HttpResponse::BadRequest().body(reason.to_string());
+    }
+  }
+
+  let mut game = Game::new();
+  game.ruleset = body.ruleset;
+  game.visibility = body.visibility;
+  game.position = body.position;
+
+  match insert_game(&data, game).await {
+    Ok(response) => HttpResponse::Ok().json(response),
     Err(_) => HttpResponse::InternalServerError().body("Failed to create new game"),
   }
 }
 
+#[derive(Deserialize)]
+pub struct InviteCodePath {
+  pub code: String,
+}
+
+/// translates an invite code to the id of the game
+#[get("/invite/{code}")]
+pub async fn resolve_invite_code(
+  path: web::Path<InviteCodePath>,
+  data: web::Data<AppData>,
+) -> HttpResponse {
+  let code = normalize_invite_code(&path.code);
+  match database::find_game_id_by_invite_code(&data.db, &code).await {
+    Ok(Some(id)) => HttpResponse::Ok().body(id),
+    Ok(None) => HttpResponse::NotFound().body("Invalid invite code"),
+    Err(_) => HttpResponse::InternalServerError().body("Failed to find game"),
+  }
+}
+
+#[derive(Deserialize)]
+pub struct ExportPath {
+  pub room: String,
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+  /// required for private games
+  pub invite_code: Option<String>,
+}
+
+/// the game in the text notation, see `Game::to_notation`
+#[get("/{room}/export")]
+pub async fn export_game(
+  path: web::Path<ExportPath>,
+  query: web::Query<ExportQuery>,
+  data: web::Data<AppData>,
+) -> HttpResponse {
+  let game = match database::find_game(&data.db, &path.room).await {
+    Ok(Some(game)) => game,
+    Ok(None) => return HttpResponse::NotFound().body("Game does not exist"),
+    Err(_) => return HttpResponse::InternalServerError().body("Failed to find game"),
+  };
+
+  if game.visibility == Visibility::Private {
+    let invite_code = query.invite_code.as_deref().map(normalize_invite_code);
+    if invite_code.is_none() || invite_code != game.invite_code {
+      return HttpResponse::Forbidden().body("This game is private, you need an invite code");
+    }
+  }
+
+  HttpResponse::Ok()
+    .content_type("text/plain; charset=utf-8")
+    .body(game.to_notation())
+}
+
+/// creates a game from the text notation, the moves are replayed so they have to be legal,
+/// the imported players get new ids, so the game can only be watched
+#[post("/import")]
+pub async fn import_game(body: String, data: web::Data<AppData>) -> HttpResponse {
+  let mut game = match Game::from_notation(&body) {
+    Ok(game) => game,
+    Err(err) => return HttpResponse::BadRequest().body(err.to_string()),
+  };
+  for player in game.players.iter_mut().filter(|player| !player.is_bot) {
+    player.id = Uuid::new_v4().to_string();
+  }
+
+  match database::create_game(&data.db, game).await {
+    Ok(id) => HttpResponse::Ok().json(CreateGameResponse {
+      id,
+      invite_code: None,
+    }),
+    Err(_) => HttpResponse::InternalServerError().body("Failed to import game"),
+  }
+}
+
 #[derive(Deserialize)]
 pub struct JoinGameBody {
   pub name: String,
+  /// required for private games
+  #[serde(default)]
+  pub invite_code: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -41,6 +197,13 @@ pub async fn join_game(
     Ok(Some(game)) => game,
     _ => return HttpResponse::InternalServerError().body("Failed to join game"),
   };
+
+  if game.visibility == Visibility::Private {
+    let invite_code = body.invite_code.as_deref().map(normalize_invite_code);
+    if invite_code.is_none() || invite_code != game.invite_code {
+      return HttpResponse::Forbidden().body("This game is private, you need an invite code");
+    }
+  }
   let used_colors = game
     .players
     .iter()
@@ -79,6 +242,7 @@ pub async fn init_websocket(
   req: HttpRequest,
   stream: web::Payload,
   path: web::Path<WebsocketPath>,
+  query: web::Query<WireFormatQuery>,
   data: web::Data<AppData>,
 ) -> HttpResponse {
   let game_id = &path.room;
@@ -99,23 +263,100 @@ pub async fn init_websocket(
     player_id.clone(),
     game_id.clone(),
     data.game_server_addr.clone(),
+    false,
+    query.encoding,
   );
   let resp = ws::start(session, &req, stream);
-  println!("{:?}", resp);
+  if let Err(e) = &resp {
+    warn!(room_id = %game_id, player_id = %player_id, error = %e, "websocket handshake failed");
+  }
   resp.unwrap_or_else(|_| HttpResponse::InternalServerError().body("Whoops"))
 }
 
-#[get("")]
-pub async fn get_games(data: web::Data<AppData>) -> HttpResponse {
-  let db = &data.db.lock().await;
-  let game_collection = db.collection::<Game>("games");
+const DEFAULT_PAGE_SIZE: u64 = 20;
+const MAX_PAGE_SIZE: u64 = 50;
+/// keeps the offset of the page far from overflowing, nobody browses that far
+const MAX_PAGE: u64 = 10_000;
+
+#[derive(Deserialize)]
+pub struct GamesQuery {
+  pub status: Option<GameStatus>,
+  #[serde(default)]
+  pub page: u64,
+  pub per_page: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct SpectatePath {
+  pub room: String,
+}
+
+#[derive(Deserialize)]
+pub struct SpectateQuery {
+  /// required for private games
+  pub invite_code: Option<String>,
+  #[serde(default)]
+  pub encoding: WireFormat,
+}
+
+/// read-only websocket, spectators receive all updates of the game
+#[get("/spectate/{room}")]
+pub async fn spectate_websocket(
+  req: HttpRequest,
+  stream: web::Payload,
+  path: web::Path<SpectatePath>,
+  query: web::Query<SpectateQuery>,
+  data: web::Data<AppData>,
+) -> HttpResponse {
+  let game_id = &path.room;
 
-  let mut cursor = game_collection.find(None, None).await.unwrap();
+  let game = match database::find_game(&data.db, game_id).await {
+    Ok(Some(game)) => game,
+    _ => return HttpResponse::Forbidden().body("Game does not exist"),
+  };
 
-  let mut games: Vec<Game> = Vec::new();
-  while let Ok(Some(game)) = cursor.try_next().await {
-    games.push(game);
+  if game.visibility == Visibility::Private {
+    let invite_code = query.invite_code.as_deref().map(normalize_invite_code);
+    if invite_code.is_none() || invite_code != game.invite_code {
+      return HttpResponse::Forbidden().body("This game is private, you need an invite code");
+    }
   }
 
-  HttpResponse::Ok().json(games)
+  let session = GameSession::new(
+    Uuid::new_v4().to_string(),
+    game_id.clone(),
+    data.game_server_addr.clone(),
+    true,
+    query.encoding,
+  );
+  ws::start(session, &req, stream)
+    .unwrap_or_else(|_| HttpResponse::InternalServerError().body("Whoops"))
+}
+
+/// websocket for players waiting for a quick match
+#[get("/matchmaking")]
+pub async fn join_matchmaking(
+  req: HttpRequest,
+  stream: web::Payload,
+  data: web::Data<AppData>,
+) -> HttpResponse {
+  let session = MatchmakingSession::new(Uuid::new_v4().to_string(), data.matchmaker_addr.clone());
+  ws::start(session, &req, stream)
+    .unwrap_or_else(|_| HttpResponse::InternalServerError().body("Whoops"))
+}
+
+#[get("")]
+pub async fn get_games(query: web::Query<GamesQuery>, data: web::Data<AppData>) -> HttpResponse {
+  let per_page = query
+    .per_page
+    .unwrap_or(DEFAULT_PAGE_SIZE)
+    .clamp(1, MAX_PAGE_SIZE);
+  if query.page > MAX_PAGE {
+    return HttpResponse::BadRequest().body(format!("Page can be at most {}", MAX_PAGE));
+  }
+
+  match database::list_games(&data.db, query.status, query.page, per_page).await {
+    Ok(games) => HttpResponse::Ok().json(games),
+    Err(_) => HttpResponse::InternalServerError().body("Failed to list games"),
+  }
 }
@@ -1,19 +1,65 @@
 use anyhow::anyhow;
+use futures::stream::TryStreamExt;
 use mongodb::{
   bson::{self, doc, oid::ObjectId, Bson, Document},
-  options::{FindOneAndUpdateOptions, ReturnDocument},
-  Database,
+  error::{ErrorKind, WriteFailure},
+  options::{FindOneAndUpdateOptions, FindOptions, IndexOptions, ReplaceOptions, ReturnDocument},
+  Database, IndexModel,
 };
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 use tokio::sync::Mutex;
 
-use crate::models::{game::Game, player::Player};
+use crate::models::{
+  chat::ChatMessage,
+  game::Game,
+  game_summary::{GameListPage, GameStatus, GameSummary},
+  player::Player,
+  snapshot::Snapshot,
+};
+use crate::utils::metrics::metrics;
 
-pub async fn create_game(db: &Arc<Mutex<Database>>) -> anyhow::Result<String> {
+/// invite codes are unique among the games which have one, public games store `null`
+/// so they are left out by the filter rather than by a sparse index
+pub async fn create_indexes(db: &Arc<Mutex<Database>>) -> anyhow::Result<()> {
+  let db_mutex = db.lock().await;
+  let game_collection = db_mutex.collection::<Document>("games");
+  let options = IndexOptions::builder()
+    .unique(true)
+    .partial_filter_expression(doc! { "invite_code": { "$type": "string" } })
+    .build();
+  let index = IndexModel::builder()
+    .keys(doc! { "invite_code": 1 })
+    .options(options)
+    .build();
+  game_collection.create_index(index, None).await?;
+  Ok(())
+}
+
+/// another game already has the invite code of the inserted one
+#[derive(Debug)]
+pub struct DuplicateInviteCode;
+
+impl fmt::Display for DuplicateInviteCode {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "the invite code is already used")
+  }
+}
+
+impl std::error::Error for DuplicateInviteCode {}
+
+fn is_duplicate_key(error: &mongodb::error::Error) -> bool {
+  match &*error.kind {
+    ErrorKind::Write(WriteFailure::WriteError(write_error)) => write_error.code == 11000,
+    _ => false,
+  }
+}
+
+/// fails with `DuplicateInviteCode` if the invite code of `game` is taken
+pub async fn create_game(db: &Arc<Mutex<Database>>, game: Game) -> anyhow::Result<String> {
+  let _timer = metrics().storage_timer("create_game");
   let db_mutex = db.lock().await;
   let game_collection = db_mutex.collection::<Game>("games");
-  let mock_game = Game::new();
-  let res = game_collection.insert_one(mock_game, None).await;
+  let res = game_collection.insert_one(game, None).await;
   match res {
     Ok(result) => {
       if let Bson::ObjectId(id) = result.inserted_id {
@@ -22,6 +68,7 @@ pub async fn create_game(db: &Arc<Mutex<Database>>) -> anyhow::Result<String> {
         return Err(anyhow!("game id couldn't be parsed"));
       };
     }
+    Err(e) if is_duplicate_key(&e) => Err(DuplicateInviteCode.into()),
     Err(e) => Err(anyhow!(e)),
   }
 }
@@ -31,6 +78,7 @@ pub async fn add_player(
   game_id: &str,
   new_player: Player,
 ) -> anyhow::Result<Game> {
+  let _timer = metrics().storage_timer("add_player");
   let serialized_player = bson::to_bson(&new_player)?;
   let update = doc! { "$push": { "players": serialized_player } };
   let oid = match ObjectId::parse_str(game_id) {
@@ -42,6 +90,7 @@ pub async fn add_player(
 }
 
 pub async fn find_game(db: &Arc<Mutex<Database>>, game_id: &str) -> anyhow::Result<Option<Game>> {
+  let _timer = metrics().storage_timer("find_game");
   let db_mutex = db.lock().await;
   let game_collection = db_mutex.collection::<Game>("games");
   let oid = match ObjectId::parse_str(game_id) {
@@ -56,14 +105,201 @@ pub async fn find_game(db: &Arc<Mutex<Database>>, game_id: &str) -> anyhow::Resu
   }
 }
 
-pub async fn start_game(db: &Arc<Mutex<Database>>, game_id: &str) -> anyhow::Result<Game> {
-  let oid = match ObjectId::parse_str(game_id) {
-    Ok(res) => res,
-    Err(err) => return Err(anyhow!(err)),
+pub async fn find_game_id_by_invite_code(
+  db: &Arc<Mutex<Database>>,
+  invite_code: &str,
+) -> anyhow::Result<Option<String>> {
+  let _timer = metrics().storage_timer("find_game_id_by_invite_code");
+  let db_mutex = db.lock().await;
+  let game_collection = db_mutex.collection::<Document>("games");
+  let filter = doc! { "invite_code": invite_code };
+  match game_collection.find_one(filter, None).await? {
+    Some(document) => Ok(Some(document.get_object_id("_id")?.to_hex())),
+    None => Ok(None),
+  }
+}
+
+/// private games never show up in the listing
+fn status_filter(status: Option<GameStatus>) -> Document {
+  let mut filter = match status {
+    Some(GameStatus::Lobby) => {
+      doc! { "started": false, "winner": Bson::Null, "players.3": { "$exists": false } }
+    }
+    Some(GameStatus::InProgress) => doc! { "started": true, "winner": Bson::Null },
+    Some(GameStatus::Finished) => doc! { "winner": { "$ne": Bson::Null } },
+    None => doc! {},
   };
-  let filter = doc! { "_id" : oid };
+  filter.insert("visibility", doc! { "$ne": "Private" });
+  filter
+}
+
+/// newest games first, `page` is indexed from 0
+pub async fn list_games(
+  db: &Arc<Mutex<Database>>,
+  status: Option<GameStatus>,
+  page: u64,
+  per_page: u64,
+) -> anyhow::Result<GameListPage> {
+  let _timer = metrics().storage_timer("list_games");
+  let db_mutex = db.lock().await;
+  let game_collection = db_mutex.collection::<Document>("games");
+  let filter = status_filter(status);
+  let skip = page
+    .checked_mul(per_page)
+    .ok_or_else(|| anyhow!("page {} is out of range", page))?;
+
+  let total = game_collection.count_documents(filter.clone(), None).await?;
+  let options = FindOptions::builder()
+    .sort(doc! { "_id": -1 })
+    .skip(skip)
+    .limit(per_page as i64)
+    .build();
+  let mut cursor = game_collection.find(filter, options).await?;
+
+  let mut games: Vec<GameSummary> = Vec::new();
+  while let Some(document) = cursor.try_next().await? {
+    let id = document.get_object_id("_id")?.to_hex();
+    let game: Game = bson::from_document(document)?;
+    games.push(GameSummary::new(id, &game));
+  }
+
+  Ok(GameListPage {
+    games,
+    page,
+    per_page,
+    total,
+  })
+}
+
+/// number of chat messages delivered to newly connected clients
+const CHAT_HISTORY_SIZE: i64 = 50;
+
+pub async fn add_chat_message(
+  db: &Arc<Mutex<Database>>,
+  message: &ChatMessage,
+) -> anyhow::Result<()> {
+  let _timer = metrics().storage_timer("add_chat_message");
+  let db_mutex = db.lock().await;
+  let chat_collection = db_mutex.collection::<ChatMessage>("chat");
+  chat_collection.insert_one(message, None).await?;
+  Ok(())
+}
+
+/// latest messages of the game, oldest first
+pub async fn find_chat_history(
+  db: &Arc<Mutex<Database>>,
+  game_id: &str,
+) -> anyhow::Result<Vec<ChatMessage>> {
+  let _timer = metrics().storage_timer("find_chat_history");
+  let db_mutex = db.lock().await;
+  let chat_collection = db_mutex.collection::<ChatMessage>("chat");
+  let options = FindOptions::builder()
+    .sort(doc! { "_id": -1 })
+    .limit(CHAT_HISTORY_SIZE)
+    .build();
+  let cursor = chat_collection
+    .find(doc! { "game_id": game_id }, options)
+    .await?;
+  let mut history: Vec<ChatMessage> = cursor.try_collect().await?;
+  history.reverse();
+  Ok(history)
+}
+
+/// keeps only the latest snapshot of every game
+pub async fn save_snapshot(db: &Arc<Mutex<Database>>, snapshot: &Snapshot) -> anyhow::Result<()> {
+  let _timer = metrics().storage_timer("save_snapshot");
+  let db_mutex = db.lock().await;
+  let snapshot_collection = db_mutex.collection::<Snapshot>("snapshots");
+  let options = ReplaceOptions::builder().upsert(true).build();
+  snapshot_collection
+    .replace_one(doc! { "game_id": &snapshot.game_id }, snapshot, options)
+    .await?;
+  Ok(())
+}
+
+pub async fn find_snapshot(
+  db: &Arc<Mutex<Database>>,
+  game_id: &str,
+) -> anyhow::Result<Option<Snapshot>> {
+  let _timer = metrics().storage_timer("find_snapshot");
+  let db_mutex = db.lock().await;
+  let snapshot_collection = db_mutex.collection::<Snapshot>("snapshots");
+  Ok(
+    snapshot_collection
+      .find_one(doc! { "game_id": game_id }, None)
+      .await?,
+  )
+}
+
+pub async fn delete_snapshot(db: &Arc<Mutex<Database>>, game_id: &str) -> anyhow::Result<()> {
+  let _timer = metrics().storage_timer("delete_snapshot");
+  let db_mutex = db.lock().await;
+  let snapshot_collection = db_mutex.collection::<Snapshot>("snapshots");
+  snapshot_collection
+    .delete_one(doc! { "game_id": game_id }, None)
+    .await?;
+  Ok(())
+}
+
+/// marks the undo of the move at `sequence` as requested, `None` if it's already requested
+/// or the snapshot belongs to another move
+pub async fn request_undo(
+  db: &Arc<Mutex<Database>>,
+  game_id: &str,
+  sequence: u64,
+) -> anyhow::Result<Option<Snapshot>> {
+  let _timer = metrics().storage_timer("request_undo");
+  let filter = doc! { "game_id": game_id, "sequence": sequence as i64, "undo_requested": false };
+  let update = doc! { "$set": { "undo_requested": true, "accepted": [] } };
+  update_snapshot(db, filter, update).await
+}
+
+/// adds the player to those who agreed with the requested undo
+pub async fn accept_undo(
+  db: &Arc<Mutex<Database>>,
+  game_id: &str,
+  player_id: &str,
+) -> anyhow::Result<Option<Snapshot>> {
+  let _timer = metrics().storage_timer("accept_undo");
+  let filter = doc! { "game_id": game_id, "undo_requested": true };
+  let update = doc! { "$addToSet": { "accepted": player_id } };
+  update_snapshot(db, filter, update).await
+}
+
+async fn update_snapshot(
+  db: &Arc<Mutex<Database>>,
+  filter: Document,
+  update: Document,
+) -> anyhow::Result<Option<Snapshot>> {
+  let db_mutex = db.lock().await;
+  let snapshot_collection = db_mutex.collection::<Snapshot>("snapshots");
+  let option = FindOneAndUpdateOptions::builder()
+    .return_document(ReturnDocument::After)
+    .build();
+  Ok(
+    snapshot_collection
+      .find_one_and_update(filter, update, option)
+      .await?,
+  )
+}
+
+/// `None` if the game doesn't exist or it has already been started
+pub async fn start_game(db: &Arc<Mutex<Database>>, game_id: &str) -> anyhow::Result<Option<Game>> {
+  let _timer = metrics().storage_timer("start_game");
+  let oid = ObjectId::parse_str(game_id)?;
+  let filter = doc! { "_id" : oid, "started": false };
   let update = doc! { "$set": { "started" : true } };
-  return update_game(db, filter, update).await;
+
+  let db_mutex = db.lock().await;
+  let game_collection = db_mutex.collection::<Game>("games");
+  let option = FindOneAndUpdateOptions::builder()
+    .return_document(ReturnDocument::After)
+    .build();
+  Ok(
+    game_collection
+      .find_one_and_update(filter, update, option)
+      .await?,
+  )
 }
 
 pub async fn add_dice_roll(
@@ -71,6 +307,7 @@ pub async fn add_dice_roll(
   game_id: &str,
   roll: usize,
 ) -> anyhow::Result<Game> {
+  let _timer = metrics().storage_timer("add_dice_roll");
   let oid = match ObjectId::parse_str(game_id) {
     Ok(res) => res,
     Err(err) => return Err(anyhow!(err)),
@@ -118,11 +355,59 @@ pub async fn update(
   }
 }
 
+/// the stored game has changed since `Game::sequence`, e.g. a move was taken back meanwhile
+#[derive(Debug)]
+pub struct OutdatedGame;
+
+impl fmt::Display for OutdatedGame {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "the game has changed in the meantime")
+  }
+}
+
+impl std::error::Error for OutdatedGame {}
+
+/// saves the game and bumps its sequence number, returns the game before the update
+/// together with the saved game, fails with `OutdatedGame` unless the stored game still
+/// has the sequence of `game`
+pub async fn advance_game_state(
+  db: &Arc<Mutex<Database>>,
+  game_id: &str,
+  game: &Game,
+) -> anyhow::Result<(Game, Game)> {
+  let _timer = metrics().storage_timer("advance_game_state");
+  let oid = ObjectId::parse_str(game_id)?;
+  let mut update = make_doc(game)?;
+  update.insert("$inc", doc! { "sequence": 1_i64 });
+  // games from before the sequence was introduced don't have it stored
+  let sequence = match game.sequence {
+    0 => doc! { "$in": [0_i64, Bson::Null] },
+    sequence => doc! { "$eq": sequence as i64 },
+  };
+
+  let db_mutex = db.lock().await;
+  let game_collection = db_mutex.collection::<Game>("games");
+  let option = FindOneAndUpdateOptions::builder()
+    .return_document(ReturnDocument::Before)
+    .build();
+  let previous = game_collection
+    .find_one_and_update(doc! { "_id": oid, "sequence": sequence }, update, option)
+    .await?
+    .ok_or(OutdatedGame)?;
+
+  let current = Game {
+    sequence: previous.sequence + 1,
+    ..game.clone()
+  };
+  Ok((previous, current))
+}
+
 pub async fn update_game_state(
   db: &Arc<Mutex<Database>>,
   game_id: &str,
   game: &Game,
 ) -> anyhow::Result<Game> {
+  let _timer = metrics().storage_timer("update_game_state");
   let update_doc = match make_doc(game) {
     Ok(doc) => doc,
     _ => return Err(anyhow!("Failed to create document")),
@@ -131,11 +416,14 @@ pub async fn update_game_state(
 }
 
 fn make_doc(game: &Game) -> anyhow::Result<Document> {
-  let fields = bson::to_bson(&game.fields)?;
   let players = bson::to_bson(&game.players)?;
   let current_player = bson::to_bson(&game.current_player)?;
   let bson_dice_throws = bson::to_bson(&game.dice_throws)?;
   let phase = bson::to_bson(&game.round_phase)?;
-  let doc = doc! { "$set": { "fields": fields, "players": players, "current_player": current_player, "dice_throws": &bson_dice_throws, "round_phase":phase } };
+  let winner = bson::to_bson(&game.winner)?;
+  let placements = bson::to_bson(&game.placements)?;
+  let turns = bson::to_bson(&game.turns)?;
+  let current_turn = bson::to_bson(&game.current_turn)?;
+  let doc = doc! { "$set": { "players": players, "current_player": current_player, "dice_throws": &bson_dice_throws, "round_phase":phase, "winner": winner, "placements": placements, "turns": turns, "current_turn": current_turn } };
   Ok(doc)
 }
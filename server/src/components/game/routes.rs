@@ -7,7 +7,12 @@ pub fn attach_routes(config: &mut web::ServiceConfig) {
     web::scope("/games")
       .service(controller::get_games)
       .service(controller::create_new_game)
+      .service(controller::resolve_invite_code)
+      .service(controller::import_game)
+      .service(controller::export_game)
       .service(controller::join_game)
-      .service(controller::init_websocket),
+      .service(controller::init_websocket)
+      .service(controller::spectate_websocket)
+      .service(controller::join_matchmaking),
   );
 }
@@ -1,32 +1,63 @@
-use actix::prelude::{Actor, Context, Handler, Recipient};
+use actix::prelude::{Actor, AsyncContext, Context, Handler, Recipient};
 use mongodb::Database;
 use std::{
-  collections::{HashMap, HashSet},
+  collections::{HashMap, HashSet, VecDeque},
   sync::Arc,
+  time::{Duration, Instant},
 };
 use tokio::sync::Mutex;
+use tracing::{debug, info, info_span, warn, Instrument};
 
 use super::{
   services::{
-    connect_client::connect_client, move_piece::move_piece, promote_piece::promote_piece,
-    roll_die::roll_dice, start_game::start_game,
+    connect_client::connect_client,
+    connect_spectator::connect_spectator,
+    move_piece::move_piece,
+    promote_piece::promote_piece,
+    roll_die::roll_dice,
+    send_chat::send_chat,
+    skip_idle_turn::skip_idle_turn,
+    start_game::start_game,
+    sync_game::sync_game,
+    undo::{answer_undo, request_undo},
   },
-  utils::send_message_to_room,
+  utils::{send_error, send_message_to_room},
 };
 use crate::models::{
   actor_messages::{ClientActorMessage, Connect, Disconnect, WsMessage},
-  position::Position,
+  chat::ChatContent,
+  server_config::ServerConfig,
 };
-use crate::utils::enums::ClientMessage;
 use crate::utils::enums::ServerMessage;
+use crate::utils::enums::{ClientMessage, ErrorCode, MoveType};
+use crate::utils::metrics::metrics;
+use crate::utils::profanity::filter_profanity;
 
 type Session = Recipient<WsMessage>;
+/// room_id => when the game last changed while a human was on turn, see `refresh_activity`
+type LastActivity = Arc<std::sync::Mutex<HashMap<String, Instant>>>;
+
+/// how often idle rooms are looked for if turns time out
+const TURN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Clone)]
 pub struct GameServerState {
   pub db: Arc<Mutex<Database>>,
   pub sessions: HashMap<String, Session>,
   pub rooms: HashMap<String, HashSet<String>>,
+  pub config: Arc<ServerConfig>,
+  last_activity: LastActivity,
+}
+
+impl GameServerState {
+  /// the turn timeout of the room starts over, called only when the game changes,
+  /// so commands of spectators or of players who aren't on turn don't keep a room alive.
+  /// Rooms without players aren't tracked
+  pub fn refresh_activity(&self, room_id: &str) {
+    if let Some(since) = self.last_activity.lock().unwrap().get_mut(room_id) {
+      *since = Instant::now();
+    }
+  }
 }
 
 /// GameServer actor which keeps track of all the sessions and game rooms (each game room has up to 4 sessions)
@@ -34,14 +65,22 @@ pub struct GameServer {
   db: Arc<Mutex<Database>>,
   sessions: HashMap<String, Session>, // player_id => Address to send messages
   rooms: HashMap<String, HashSet<String>>, // room_id / game_id => player_id
+  spectators: HashSet<String>,        // session ids of read-only sessions
+  recent_chats: HashMap<String, VecDeque<Instant>>, // player_id => times of recent chat messages
+  last_activity: LastActivity,
+  config: Arc<ServerConfig>,
 }
 
 impl GameServer {
-  pub fn new(db: Arc<Mutex<Database>>) -> Self {
+  pub fn new(db: Arc<Mutex<Database>>, config: Arc<ServerConfig>) -> Self {
     GameServer {
       db,
       sessions: HashMap::new(),
       rooms: HashMap::new(),
+      spectators: HashSet::new(),
+      recent_chats: HashMap::new(),
+      last_activity: LastActivity::default(),
+      config,
     }
   }
 
@@ -50,6 +89,109 @@ impl GameServer {
       db: self.db.clone(),
       sessions: self.sessions.clone(),
       rooms: self.rooms.clone(),
+      config: self.config.clone(),
+      last_activity: self.last_activity.clone(),
+    }
+  }
+
+  /// records the message if the player is still within the chat rate limit
+  fn allow_chat_message(&mut self, player_id: &str) -> bool {
+    let now = Instant::now();
+    let sent = self.recent_chats.entry(player_id.to_owned()).or_default();
+    let window = self.config.rooms.chat_rate_window();
+    while matches!(sent.front(), Some(time) if now.duration_since(*time) > window) {
+      sent.pop_front();
+    }
+    if sent.len() >= self.config.rooms.chat_rate_limit {
+      return false;
+    }
+    sent.push_back(now);
+    true
+  }
+
+  /// validates chat content before it is broadcast, returns the reason of rejection otherwise
+  fn prepare_chat(
+    &mut self,
+    player_id: &str,
+    content: ChatContent,
+  ) -> Result<ChatContent, ErrorCode> {
+    let content = match content {
+      ChatContent::Text(text) => {
+        let text = text.trim();
+        if text.is_empty() {
+          return Err(ErrorCode::MessageEmpty);
+        }
+        let max_length = self.config.rooms.max_chat_length;
+        if text.chars().count() > max_length {
+          return Err(ErrorCode::MessageTooLong { max_length });
+        }
+        ChatContent::Text(filter_profanity(text))
+      }
+      emote => emote,
+    };
+
+    if !self.allow_chat_message(player_id) {
+      return Err(ErrorCode::RateLimited);
+    }
+    Ok(content)
+  }
+
+  /// number of players and spectators connected to the room
+  fn counts(&self, room_id: &str) -> (usize, usize) {
+    let sessions = match self.rooms.get(room_id) {
+      Some(sessions) => sessions,
+      None => return (0, 0),
+    };
+    let spectator_count = sessions
+      .iter()
+      .filter(|id| self.spectators.contains(*id))
+      .count();
+    (sessions.len() - spectator_count, spectator_count)
+  }
+
+  /// refreshes the connection gauges of `/metrics`
+  fn update_gauges(&self) {
+    let active_rooms = self
+      .rooms
+      .keys()
+      .filter(|room_id| self.counts(room_id).0 > 0)
+      .count();
+    metrics().active_rooms.set(active_rooms as i64);
+    metrics().sessions.set(self.sessions.len() as i64);
+    metrics().spectators.set(self.spectators.len() as i64);
+  }
+
+  /// sends the current player and spectator count to everyone in the room
+  fn broadcast_counts(&self, room_id: &str) {
+    let (player_count, spectator_count) = self.counts(room_id);
+
+    for message in &[
+      ServerMessage::PlayerCountChange(player_count),
+      ServerMessage::SpectatorCountChange(spectator_count),
+    ] {
+      send_message_to_room(message, self.sessions.clone(), self.rooms.clone(), room_id);
+    }
+  }
+
+  /// skips the player on turn in rooms where nobody has played for longer than the turn timeout
+  fn check_turn_timeouts(&mut self, timeout: Duration) {
+    let now = Instant::now();
+    let mut last_activity = self.last_activity.lock().unwrap();
+    let idle_rooms: Vec<String> = last_activity
+      .iter()
+      .filter(|(room_id, since)| {
+        now.duration_since(**since) > timeout && self.counts(room_id).0 > 0
+      })
+      .map(|(room_id, _)| room_id.clone())
+      .collect();
+
+    for room_id in idle_rooms {
+      // the next player gets the whole timeout again
+      last_activity.insert(room_id.clone(), now);
+      let span = info_span!("turn_timeout", room_id = %room_id);
+      info!(parent: &span, "room is idle, skipping the player on turn");
+      let state = self.get_state();
+      actix_web::rt::spawn(skip_idle_turn(state, room_id).instrument(span));
     }
   }
 }
@@ -57,14 +199,43 @@ impl GameServer {
 // Make the game server an actor so it can receive and send messages to sessions
 impl Actor for GameServer {
   type Context = Context<Self>;
+
+  fn started(&mut self, ctx: &mut Self::Context) {
+    if let Some(timeout) = self.config.turns.timeout() {
+      ctx.run_interval(TURN_CHECK_INTERVAL, move |act, _| {
+        act.check_turn_timeouts(timeout)
+      });
+    }
+  }
 }
 
 // Connect a session to the GameServer
 impl Handler<Connect> for GameServer {
-  type Result = ();
+  type Result = Result<(), ErrorCode>;
 
-  fn handle(&mut self, msg: Connect, _: &mut Context<Self>) {
-    println!("connected player with id: {}", msg.player_id);
+  /// the session closes itself if it is rejected
+  fn handle(&mut self, msg: Connect, _: &mut Context<Self>) -> Self::Result {
+    let span = info_span!(
+      "connect",
+      room_id = %msg.room_id,
+      player_id = %msg.player_id,
+      spectator = msg.is_spectator
+    );
+    let _entered = span.enter();
+    info!("session connected");
+    let max_spectators = self.config.rooms.max_spectators;
+    if msg.is_spectator && self.counts(&msg.room_id).1 >= max_spectators {
+      warn!(max_spectators, "spectator rejected, the room is full");
+      return Err(ErrorCode::TooManySpectators { max_spectators });
+    }
+    if !msg.is_spectator {
+      self
+        .last_activity
+        .lock()
+        .unwrap()
+        .entry(msg.room_id.clone())
+        .or_insert_with(Instant::now);
+    }
     self
       .sessions
       .insert(msg.player_id.clone(), msg.address.clone());
@@ -73,26 +244,26 @@ impl Handler<Connect> for GameServer {
       .entry(msg.room_id.clone())
       .or_insert_with(HashSet::new)
       .insert(msg.player_id.clone());
+    if msg.is_spectator {
+      self.spectators.insert(msg.player_id.clone());
+    }
 
-    let count = self.sessions.len();
-    let server_msg = ServerMessage::PlayerCountChange(count);
-    let json = serde_json::to_string(&server_msg).unwrap();
-
-    send_message_to_room(
-      json.as_str(),
-      self.sessions.clone(),
-      self.rooms.clone(),
-      msg.room_id.as_str(),
-    );
+    self.broadcast_counts(msg.room_id.as_str());
+    self.update_gauges();
 
     let state = self.get_state();
-    actix_web::rt::spawn(async move {
-      println!(
-        "responded with connect message to player: {}",
-        msg.player_id
-      );
-      connect_client(state, &msg).await;
-    });
+    actix_web::rt::spawn(
+      async move {
+        if msg.is_spectator {
+          connect_spectator(state, &msg).await;
+        } else {
+          connect_client(state, &msg).await;
+        }
+        debug!("responded with the game");
+      }
+      .instrument(span.clone()),
+    );
+    Ok(())
   }
 }
 
@@ -101,7 +272,7 @@ impl Handler<Disconnect> for GameServer {
   type Result = ();
 
   fn handle(&mut self, msg: Disconnect, _: &mut Context<Self>) {
-    println!("Someone left the game");
+    info!(room_id = %msg.room_id, player_id = %msg.player_id, "session disconnected");
 
     let mut rooms: Vec<String> = Vec::new();
 
@@ -112,18 +283,16 @@ impl Handler<Disconnect> for GameServer {
         }
       }
     }
-
-    let server_msg = ServerMessage::PlayerCountChange(self.sessions.len());
-    let json = serde_json::to_string(&server_msg).unwrap();
+    self.spectators.remove(&msg.player_id);
+    self.recent_chats.remove(&msg.player_id);
 
     for room in rooms {
-      send_message_to_room(
-        json.as_str(),
-        self.sessions.clone(),
-        self.rooms.clone(),
-        room.as_str(),
-      );
+      if self.counts(&room).0 == 0 {
+        self.last_activity.lock().unwrap().remove(&room);
+      }
+      self.broadcast_counts(room.as_str());
     }
+    self.update_gauges();
   }
 }
 
@@ -131,32 +300,76 @@ impl Handler<ClientActorMessage> for GameServer {
   type Result = ();
 
   fn handle(&mut self, msg: ClientActorMessage, _ctx: &mut Context<Self>) {
-    let result = serde_json::from_str::<ClientMessage>(msg.content.as_str());
+    let message = msg.message.clone();
+    // follows the command into the spawned service, a game id is enough to find its events
+    let span = info_span!(
+      "command",
+      room_id = %msg.room_id,
+      player_id = %msg.player_id,
+      message = message.name(),
+      request_id = msg.request_id.as_deref().unwrap_or_default()
+    );
+    let _entered = span.enter();
+    debug!("command received");
 
-    let message = match result {
-      Ok(message) => message,
-      // TODO: handle errors [send back Error(String) message ??]
-      Err(_) => return,
-    };
+    // spectators only receive the game, but they may ask for all of it
+    let read_only = !matches!(message, ClientMessage::RequestSync);
+    if read_only && self.spectators.contains(&msg.player_id) {
+      debug!("spectators can't send commands");
+      send_error(
+        ErrorCode::SpectatorNotAllowed,
+        msg.request_id,
+        self.sessions.clone(),
+        &msg.player_id,
+      );
+      return;
+    }
 
-    let state = self.get_state();
-    actix_web::rt::spawn(async move {
-      match message {
-        ClientMessage::ThrowDice => roll_dice(state, msg).await,
-        ClientMessage::MoveFigure(position, color) => {
-          move_piece(
-            state,
-            msg,
-            Position {
-              position,
-              is_home: color.is_some(),
-            },
+    let chat = match &message {
+      ClientMessage::Chat(text) => Some(ChatContent::Text(text.clone())),
+      ClientMessage::Emote(emote) => Some(ChatContent::Emote(*emote)),
+      _ => None,
+    };
+    if let Some(content) = chat {
+      match self.prepare_chat(&msg.player_id, content) {
+        Ok(content) => {
+          let state = self.get_state();
+          actix_web::rt::spawn(send_chat(state, msg, content).instrument(span.clone()));
+        }
+        Err(code) => {
+          debug!(?code, "chat message rejected");
+          send_error(
+            code,
+            msg.request_id.clone(),
+            self.sessions.clone(),
+            &msg.player_id,
           )
-          .await
         }
-        ClientMessage::PromotePiece => promote_piece(state, msg).await,
-        ClientMessage::StartGame => start_game(state, msg).await,
-      };
-    });
+      }
+      return;
+    }
+
+    let state = self.get_state();
+    actix_web::rt::spawn(
+      async move {
+        match message {
+          ClientMessage::ThrowDice => roll_dice(state, msg).await,
+          ClientMessage::MoveFigure(piece_id) => {
+            move_piece(state, msg, MoveType::Move(piece_id)).await
+          }
+          ClientMessage::MoveFigureWithDie(piece_id, die_index) => {
+            move_piece(state, msg, MoveType::MoveWithDie(piece_id, die_index)).await
+          }
+          ClientMessage::PromotePiece => promote_piece(state, msg).await,
+          ClientMessage::StartGame => start_game(state, msg).await,
+          ClientMessage::RequestSync => sync_game(state, msg).await,
+          ClientMessage::RequestUndo => request_undo(state, msg).await,
+          ClientMessage::AnswerUndo(accepted) => answer_undo(state, msg, accepted).await,
+          // chat is rate limited synchronously above
+          ClientMessage::Chat(_) | ClientMessage::Emote(_) => {}
+        };
+      }
+      .instrument(span.clone()),
+    );
   }
 }
@@ -1,10 +1,13 @@
 use crate::{
   components::{
     game::database,
-    game_server::{actor::GameServerState, utils::send_message},
+    game_server::{
+      actor::GameServerState,
+      utils::{send_error, send_message},
+    },
   },
   models::actor_messages::Connect,
-  utils::enums::ServerMessage,
+  utils::enums::{ErrorCode, ServerMessage},
 };
 
 pub async fn connect_client(state: GameServerState, msg: &Connect) {
@@ -13,16 +16,21 @@ pub async fn connect_client(state: GameServerState, msg: &Connect) {
   let game = match game {
     Ok(Some(game)) => game,
     Ok(None) => {
-      let message = serde_json::to_string(&ServerMessage::Error("Game not found".into())).unwrap();
-      send_message(message.as_str(), state.sessions, &msg.player_id);
+      send_error(
+        ErrorCode::GameNotFound,
+        None,
+        state.sessions,
+        &msg.player_id,
+      );
       return;
     }
     Err(_) => {
-      let message = serde_json::to_string(&ServerMessage::Error(
-        "Server couldn't connect to database".into(),
-      ))
-      .unwrap();
-      send_message(message.as_str(), state.sessions, &msg.player_id);
+      send_error(
+        ErrorCode::StorageFailure,
+        None,
+        state.sessions,
+        &msg.player_id,
+      );
       return;
     }
   };
@@ -32,16 +40,21 @@ pub async fn connect_client(state: GameServerState, msg: &Connect) {
   let player = match player {
     Some(player) => player,
     None => {
-      let message = serde_json::to_string(&ServerMessage::Error(
-        "Player with given id not found".into(),
-      ))
-      .unwrap();
-      send_message(message.as_str(), state.sessions, &msg.player_id);
+      send_error(
+        ErrorCode::PlayerNotFound,
+        None,
+        state.sessions,
+        &msg.player_id,
+      );
       return;
     }
   };
 
-  let message =
-    serde_json::to_string(&ServerMessage::ConnectResponse(game.clone(), player.color)).unwrap();
-  send_message(message.as_str(), state.sessions, &msg.player_id);
+  // missing history shouldn't prevent the player from playing
+  let history = database::find_chat_history(&state.db, &msg.room_id)
+    .await
+    .unwrap_or_default();
+
+  let message = ServerMessage::ConnectResponse(game.clone(), player.color, history);
+  send_message(&message, state.sessions, &msg.player_id);
 }
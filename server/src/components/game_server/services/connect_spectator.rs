@@ -0,0 +1,41 @@
+use crate::{
+  components::{
+    game::database,
+    game_server::{
+      actor::GameServerState,
+      utils::{send_error, send_message},
+    },
+  },
+  models::actor_messages::Connect,
+  utils::enums::{ErrorCode, ServerMessage},
+};
+
+pub async fn connect_spectator(state: GameServerState, msg: &Connect) {
+  let game = match database::find_game(&state.db, &msg.room_id).await {
+    Ok(Some(game)) => game,
+    Ok(None) => {
+      send_error(
+        ErrorCode::GameNotFound,
+        None,
+        state.sessions,
+        &msg.player_id,
+      );
+      return;
+    }
+    Err(_) => {
+      send_error(
+        ErrorCode::StorageFailure,
+        None,
+        state.sessions,
+        &msg.player_id,
+      );
+      return;
+    }
+  };
+  let history = database::find_chat_history(&state.db, &msg.room_id)
+    .await
+    .unwrap_or_default();
+
+  let message = ServerMessage::SpectateResponse(game, history);
+  send_message(&message, state.sessions, &msg.player_id);
+}
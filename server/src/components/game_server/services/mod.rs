@@ -1,7 +1,12 @@
 pub mod connect_client;
+pub mod connect_spectator;
 pub mod move_bot;
 pub mod move_piece;
 pub mod promote_piece;
 pub mod roll_die;
+pub mod send_chat;
+pub mod skip_idle_turn;
 pub mod start_game;
+pub mod sync_game;
+pub mod undo;
 pub mod utils;
@@ -1,24 +1,46 @@
+use crate::components::game::database;
 use crate::components::game_server::actor::GameServerState;
 use crate::components::game_server::services::utils::{
   send_game_update_message, send_roll_message, skip_player,
 };
 use crate::models::actor_messages::ClientActorMessage;
-use crate::models::game::Game;
+use crate::models::{game::Game, game_move::Move};
+use crate::utils::bot::choose_bot_move;
 use crate::utils::dice::get_dice_value;
 use crate::utils::enums::MoveResult;
-use tokio::time::{sleep, Duration};
+use crate::utils::metrics::metrics;
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+/// the bot's choice, the time it takes is recorded
+fn think(game: &Game, dice_value: usize) -> Option<Move> {
+  let _timer = metrics().bot_think_time.start_timer();
+  choose_bot_move(game, dice_value)
+}
+
+/// the game was replaced while the bot waited, e.g. a move was taken back,
+/// the bot's copy is outdated then and must not be saved
+async fn is_outdated(state: &GameServerState, msg: &ClientActorMessage, game: &Game) -> bool {
+  match database::find_game(&state.db, &msg.room_id).await {
+    Ok(Some(stored)) => stored.sequence != game.sequence,
+    _ => true,
+  }
+}
 
 fn move_result_update_game(game: &mut Game, move_result: MoveResult) {
   match move_result {
-    MoveResult::Success(_) => {
-      game.update_current_player();
-      game.dice_throws.clear();
-    }
+    MoveResult::Success(_) => match game.update_placements() {
+      Some(winner) => game.finish_game(winner),
+      None => {
+        game.update_current_player();
+        game.dice_throws.clear();
+      }
+    },
     MoveResult::Winner(winner) => game.finish_game(winner),
     MoveResult::Error(msg) => {
       game.update_current_player();
       game.dice_throws.clear();
-      println!("move_result_update_game - MoveResult::Error: {}", msg);
+      warn!(error = %msg, "bot chose an illegal move, passing the turn on");
     }
   }
 }
@@ -34,159 +56,96 @@ fn move_result_update_game(game: &mut Game, move_result: MoveResult) {
 /// 7. jump from home to finish
 /// 8. move piece forward in home
 /// 9. If none of the above possible, there are no valid moves, skip turn.
-
-pub async fn move_bot(state: GameServerState, msg: &ClientActorMessage, game: &mut Game) {
+///
+/// The loop ends once a human is on turn or the game is over.
+/// Every throw waits until the previous move has been animated by the clients,
+/// `animation_steps` is the length of the move which handed the turn to the bot,
+/// the pacing comes from the `bots` section of the server config.
+/// The bot stops if the game changed in the meantime (see `is_outdated`).
+
+pub async fn move_bot(
+  state: GameServerState,
+  msg: &ClientActorMessage,
+  game: &mut Game,
+  animation_steps: usize,
+) {
   let mut game = game.clone();
-  while game.is_current_player_ai() {
-    sleep(Duration::from_millis(3000)).await;
+  let mut animation_steps = animation_steps;
+  while game.winner.is_none() && game.is_current_player_ai() {
+    let bots = &state.config.bots;
+    sleep(bots.animation_pause(animation_steps) + bots.delay()).await;
+    if is_outdated(&state, msg, &game).await {
+      debug!("the game changed while the bot waited, it stops playing");
+      return;
+    }
+    let previous = game.clone();
 
-    let throw_sum = throw_dice_bot_messages(state.clone(), msg).await;
+    let rolls = throw_dice_bot_messages(state.clone(), msg).await;
+    debug!(color = ?game.current_player, ?rolls, "bot threw the dice");
+    let throw_sum: usize = rolls.iter().sum();
     // skip bot's move
     if throw_sum == 18 {
       game = skip_player(state.clone(), msg, &mut game).await;
+      animation_steps = 0;
       continue;
     }
 
-    // ----------[ handles jumping from main field ]----------
-
-    let player = game.get_current_player();
-    let positions = game.get_players_pieces_positions(player.color);
-
-    // -----[ 1. jump to finish ]-----
-
-    let piece_positions_to_jump_to_finish: Vec<usize> = positions
-      .clone()
-      .into_iter()
-      .filter(|position| game.can_jump_to_finish(*position, throw_sum))
-      .collect();
-
-    if !piece_positions_to_jump_to_finish.is_empty() {
-      let move_result = game.execute_move(piece_positions_to_jump_to_finish[0], throw_sum, false);
-      game = update_game_bot(state.clone(), msg, &mut game, move_result.clone()).await;
-      if let MoveResult::Winner(_) = move_result.clone() {
-        return;
-      }
-      continue;
-    }
-
-    // -----[ 2. jump to home ]-----
-
-    let piece_positions_to_jump_home: Vec<usize> = positions
-      .clone()
-      .into_iter()
-      .filter(|position| game.can_jump_to_home(*position, throw_sum))
-      .collect();
-
-    if !piece_positions_to_jump_home.is_empty() {
-      let move_result = game.execute_move(piece_positions_to_jump_home[0], throw_sum, false);
-      game = update_game_bot(state.clone(), msg, &mut game, move_result.clone()).await;
-      // shouldn't be necessary - player/bot should never become a winner in this branch
-      if let MoveResult::Winner(_) = move_result.clone() {
-        return;
-      }
-      continue;
-    }
-
-    // -----[ 3. add new piece if only 1 is in main field ]-----
-
-    if player.pawns_at_start + player.pawns_at_finish >= 3 && game.can_promote_piece(throw_sum) {
-      let move_result = game.promote_piece(throw_sum);
-      game = update_game_bot(state.clone(), msg, &mut game, move_result).await;
-      continue;
-    }
-
-    // -----[ 4. remove enemy's piece ]-----
-
-    let piece_positions_to_remove_enemy: Vec<usize> = positions
-      .clone()
-      .into_iter()
-      .filter(|position| game.will_remove_enemy(*position, throw_sum))
-      .collect();
-
-    if !piece_positions_to_remove_enemy.is_empty() {
-      let move_result = game.execute_move(piece_positions_to_remove_enemy[0], throw_sum, false);
-      game = update_game_bot(state.clone(), msg, &mut game, move_result.clone()).await;
-      continue;
-    }
-
-    // -----[ 5. add new piece to game ]-----
-
-    if game.can_promote_piece(throw_sum) {
-      let move_result = game.promote_piece(throw_sum);
-      game = update_game_bot(state.clone(), msg, &mut game, move_result.clone()).await;
+    if game.ruleset.split_dice {
+      game.dice_throws = rolls;
+      let (next_game, last_move_steps) = move_bot_with_split_dice(state.clone(), msg, game).await;
+      game = next_game;
+      animation_steps = last_move_steps;
       continue;
     }
 
-    // -----[ 6. move any piece (on main field) ]-----
-
-    let piece_positions_to_move: Vec<usize> = positions
-      .into_iter()
-      .filter(|position| game.can_jump(*position, throw_sum))
-      .collect();
-
-    if !piece_positions_to_move.is_empty() {
-      let move_result =
-        game.execute_move(*piece_positions_to_move.last().unwrap(), throw_sum, false);
-      game = update_game_bot(state.clone(), msg, &mut game, move_result.clone()).await;
-
-      // shouldn't be necessary, since we can only move in board
-      if let MoveResult::Winner(_) = move_result.clone() {
-        return;
+    match think(&game, throw_sum) {
+      Some(piece_move) => {
+        let move_result = game.execute_move(piece_move, throw_sum);
+        game = update_game_bot(state.clone(), msg, &mut game, move_result).await;
       }
-      continue;
-    }
-
-    // ----------[ handles jumping from home ]----------
-
-    let piece_positions_in_home: Vec<usize> =
-      game.get_players_pieces_positions_in_home(player.color);
-
-    let piece_positions_in_home_to_jump = piece_positions_in_home
-      .clone()
-      .into_iter()
-      .filter(|position| game.can_jump_from_home(*position, throw_sum))
-      .collect::<Vec<usize>>();
-
-    // -----[ 7. jump from home to finish ]-----
-
-    let piece_positions_in_home_to_jump_to_finish = piece_positions_in_home_to_jump
-      .clone()
-      .into_iter()
-      .filter(|position| game.can_jump_from_home_to_finish(*position, throw_sum))
-      .collect::<Vec<usize>>();
-
-    if !piece_positions_in_home_to_jump_to_finish.is_empty() {
-      let move_result = game.execute_move(
-        *piece_positions_in_home_to_jump_to_finish.last().unwrap(),
-        throw_sum,
-        true,
-      );
-      game = update_game_bot(state.clone(), msg, &mut game, move_result.clone()).await;
-      if let MoveResult::Winner(_) = move_result.clone() {
-        return;
-      }
-      continue;
+      // -----[ 9. no valid moves available, skip turn ]-----
+      None => game = skip_player(state.clone(), msg, &mut game).await,
     }
+    animation_steps = game.animation_steps(&previous);
+  }
+}
 
-    // -----[ 8. move piece forward in home ]-----
-
-    if !piece_positions_in_home_to_jump.is_empty() {
-      let move_result = game.execute_move(
-        *piece_positions_in_home_to_jump.last().unwrap(),
-        throw_sum,
-        true,
-      );
-      game = update_game_bot(state.clone(), msg, &mut game, move_result.clone()).await;
-
-      // shouldn't be necessary, since we can only move forward in home
-      if let MoveResult::Winner(_) = move_result.clone() {
-        return;
+/// moves with one die after another, every move is sent to the room and animated
+/// before the next one, returns the game and the animation length of the last move
+async fn move_bot_with_split_dice(
+  state: GameServerState,
+  msg: &ClientActorMessage,
+  mut game: Game,
+) -> (Game, usize) {
+  loop {
+    let previous = game.clone();
+    let chosen = game
+      .dice_throws
+      .iter()
+      .enumerate()
+      .find_map(|(die_index, &value)| {
+        think(&game, value).map(|piece_move| (die_index, value, piece_move))
+      });
+    let (die_index, value, piece_move) = match chosen {
+      Some(chosen) => chosen,
+      None => return (skip_player(state, msg, &mut game).await, 0),
+    };
+
+    let move_result = game.execute_move(piece_move, value);
+    game.dice_throws.remove(die_index);
+    if game.has_legal_moves() && game.check_winner().is_none() {
+      game = send_game_update_message(state.clone(), msg, &game).await;
+      let animation_steps = game.animation_steps(&previous);
+      sleep(state.config.bots.animation_pause(animation_steps)).await;
+      // the main loop stops the bot once it sees the outdated game
+      if is_outdated(&state, msg, &game).await {
+        return (game, 0);
       }
       continue;
     }
-
-    // -----[ 9. no valid moves available, skip turn ]-----
-    game = skip_player(state.clone(), msg, &mut game).await;
+    let game = update_game_bot(state, msg, &mut game, move_result).await;
+    let animation_steps = game.animation_steps(&previous);
+    return (game, animation_steps);
   }
 }
 
@@ -203,29 +162,17 @@ pub async fn update_game_bot(
 
 /// inform player about value on dice after each roll
 /// we don't need to keep updating game, since we perform rolling and move in the function / 'time frame'
-pub async fn throw_dice_bot_messages(state: GameServerState, msg: &ClientActorMessage) -> usize {
-  let mut throw_sum: usize = 0;
-
-  let roll = get_dice_value();
-  // can_roll_again is irrelevant
-  send_roll_message(state.clone(), msg, roll, false).await;
-  throw_sum += roll;
-
-  if roll < 6 {
-    return throw_sum;
-  }
-
-  let roll = get_dice_value();
-  send_roll_message(state.clone(), msg, roll, false).await;
-  throw_sum += roll;
-
-  if roll < 6 {
-    return throw_sum;
+pub async fn throw_dice_bot_messages(
+  state: GameServerState,
+  msg: &ClientActorMessage,
+) -> Vec<usize> {
+  let mut rolls = Vec::new();
+  // a 6 is followed by another throw, at most three throws in total
+  while rolls.len() < 3 && rolls.last().copied().unwrap_or(6) == 6 {
+    let roll = get_dice_value();
+    // can_roll_again is irrelevant
+    send_roll_message(state.clone(), msg, roll, false).await;
+    rolls.push(roll);
   }
-
-  let roll = get_dice_value();
-  send_roll_message(state.clone(), msg, roll, false).await;
-  throw_sum += roll;
-
-  throw_sum
+  rolls
 }
@@ -1,14 +1,15 @@
 use super::super::actor::GameServerState;
-use crate::components::game_server::services::move_bot::move_bot;
+use crate::components::game_server::services::{
+  move_bot::move_bot,
+  undo::remember_move,
+  utils::{broadcast_game_state, send_legal_moves},
+};
 use crate::utils::enums::RoundPhase;
 use crate::{
-  components::{
-    game::database,
-    game_server::utils::{send_message, send_message_to_room},
-  },
+  components::{game::database, game_server::utils::send_error},
   models::actor_messages::ClientActorMessage,
   utils::{
-    enums::{MoveResult, MoveType, ServerMessage},
+    enums::{ErrorCode, MoveResult, MoveType},
     game::play_round,
   },
 };
@@ -18,55 +19,80 @@ pub async fn promote_piece(state: GameServerState, msg: ClientActorMessage) {
   let mut game = match db_game {
     Ok(Some(game)) => game,
     _ => {
-      let message =
-        serde_json::to_string(&ServerMessage::Error("Cannot find game".into())).unwrap();
-      send_message(message.as_str(), state.sessions, &msg.player_id);
+      send_error(
+        ErrorCode::GameNotFound,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
       return;
     }
   };
   if game.round_phase != RoundPhase::Moving {
-    let message =
-      serde_json::to_string(&ServerMessage::Error("Promoting is not allowed now".into())).unwrap();
-    send_message(message.as_str(), state.sessions, &msg.player_id);
+    send_error(
+      ErrorCode::WrongPhase,
+      msg.request_id.clone(),
+      state.sessions,
+      &msg.player_id,
+    );
     return;
   }
   let current_player_id = game.get_current_player_id();
   if current_player_id != msg.player_id {
-    let message =
-      serde_json::to_string(&ServerMessage::Error("It is not your turn".into())).unwrap();
-    send_message(message.as_str(), state.sessions, &msg.player_id);
+    send_error(
+      ErrorCode::NotYourTurn,
+      msg.request_id.clone(),
+      state.sessions,
+      &msg.player_id,
+    );
     return;
   };
-  let result = play_round(&mut game, MoveType::Promote).await;
+  let previous = game.clone();
+  let result = play_round(&mut game, MoveType::Promote);
   match result {
     MoveResult::Success(_) => {
-      let mut game_state = database::update_game_state(&state.db, &msg.room_id, &game)
-        .await
-        .unwrap();
-      let update_message =
-        serde_json::to_string(&ServerMessage::GameUpdate(game_state.clone())).unwrap();
-      send_message_to_room(
-        update_message.as_str(),
-        state.sessions.clone(),
-        state.rooms.clone(),
-        &msg.room_id,
-      );
+      let mut game_state = match broadcast_game_state(&state, &msg.room_id, &game).await {
+        Ok(game_state) => game_state,
+        Err(_) => {
+          send_error(
+            ErrorCode::StorageFailure,
+            msg.request_id.clone(),
+            state.sessions,
+            &msg.player_id,
+          );
+          return;
+        }
+      };
+      remember_move(&state, &msg, previous.clone(), game_state.sequence).await;
 
-      // handle if next player is a bot
-      move_bot(state.clone(), &msg, &mut game_state).await;
-    }
-    MoveResult::Error(e) => {
-      let message = serde_json::to_string(&ServerMessage::Error(format!(
-        "Error executing move: {}",
-        e
-      )))
-      .unwrap();
-      send_message(message.as_str(), state.sessions, &msg.player_id);
+      match game_state.round_phase {
+        // the player has some dice left
+        RoundPhase::Moving => send_legal_moves(&state, &msg, &game_state),
+        // handle if next player is a bot
+        RoundPhase::Rolling => {
+          let animation_steps = game_state.animation_steps(&previous);
+          move_bot(state.clone(), &msg, &mut game_state, animation_steps).await
+        }
+      }
     }
-    _ => {
-      let message =
-        serde_json::to_string(&ServerMessage::Error("Promotion produced a winner".into())).unwrap();
-      send_message(message.as_str(), state.sessions, &msg.player_id);
+    MoveResult::Error(reason) => send_error(
+      ErrorCode::IllegalMove { reason },
+      msg.request_id.clone(),
+      state.sessions,
+      &msg.player_id,
+    ),
+    MoveResult::Winner(_) => {
+      if broadcast_game_state(&state, &msg.room_id, &game)
+        .await
+        .is_err()
+      {
+        send_error(
+          ErrorCode::StorageFailure,
+          msg.request_id.clone(),
+          state.sessions,
+          &msg.player_id,
+        );
+      }
     }
   }
 }
@@ -0,0 +1,64 @@
+use super::super::actor::GameServerState;
+use crate::{
+  components::{
+    game::database,
+    game_server::utils::{send_error, send_message_to_room},
+  },
+  models::{
+    actor_messages::ClientActorMessage,
+    chat::{ChatContent, ChatMessage},
+  },
+  utils::enums::{ErrorCode, ServerMessage},
+};
+
+pub async fn send_chat(state: GameServerState, msg: ClientActorMessage, content: ChatContent) {
+  let game = match database::find_game(&state.db, &msg.room_id).await {
+    Ok(Some(game)) => game,
+    _ => {
+      send_error(
+        ErrorCode::GameNotFound,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
+      return;
+    }
+  };
+
+  let player = match game.get_player_by_id(&msg.player_id) {
+    Some(player) => player,
+    None => {
+      send_error(
+        ErrorCode::PlayerNotFound,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
+      return;
+    }
+  };
+
+  let chat_message = ChatMessage {
+    game_id: msg.room_id.clone(),
+    name: player.name.clone(),
+    color: player.color,
+    content,
+    sent_at: chrono::Utc::now().timestamp_millis(),
+  };
+
+  if database::add_chat_message(&state.db, &chat_message)
+    .await
+    .is_err()
+  {
+    send_error(
+      ErrorCode::StorageFailure,
+      msg.request_id.clone(),
+      state.sessions,
+      &msg.player_id,
+    );
+    return;
+  }
+
+  let message = ServerMessage::Chat(chat_message);
+  send_message_to_room(&message, state.sessions, state.rooms, &msg.room_id);
+}
@@ -0,0 +1,30 @@
+use super::move_bot::move_bot;
+use crate::components::game::database;
+use crate::components::game_server::actor::GameServerState;
+use crate::components::game_server::services::utils::skip_player;
+use crate::models::actor_messages::ClientActorMessage;
+use crate::utils::enums::ClientMessage;
+
+/// passes the turn on once the room has been idle for longer than the turn timeout,
+/// bots are left alone since they play on their own
+pub async fn skip_idle_turn(state: GameServerState, room_id: String) {
+  let mut game = match database::find_game(&state.db, &room_id).await {
+    Ok(Some(game)) => game,
+    _ => return,
+  };
+  if !game.started || game.winner.is_some() || game.is_current_player_ai() {
+    return;
+  }
+
+  // the services expect a command of the player who is skipped
+  let msg = ClientActorMessage {
+    message: ClientMessage::RequestSync,
+    room_id,
+    player_id: game.get_current_player_id(),
+    request_id: None,
+  };
+  // the skipped turn ends the window for taking the previous move back
+  let _ = database::delete_snapshot(&state.db, &msg.room_id).await;
+  let mut game = skip_player(state.clone(), &msg, &mut game).await;
+  move_bot(state, &msg, &mut game, 0).await;
+}
@@ -1,39 +1,73 @@
-use super::super::actor::GameServerState;
+use super::{super::actor::GameServerState, move_bot::move_bot};
 use crate::{
   components::{
     game::database,
-    game_server::utils::{send_message, send_message_to_room},
+    game_server::utils::{send_error, send_message_to_room},
   },
   models::actor_messages::ClientActorMessage,
-  utils::{enums::ServerMessage, game::fill_with_bots},
+  utils::{
+    enums::{ErrorCode, ServerMessage},
+    game::fill_with_bots,
+    metrics::metrics,
+  },
 };
 
 pub async fn start_game(state: GameServerState, msg: ClientActorMessage) {
   let start_res = database::start_game(&state.db, &msg.room_id).await;
   let mut game = match start_res {
-    Ok(game) => game,
+    Ok(Some(game)) => game,
+    // starting again would set the position up once more
+    Ok(None) => {
+      send_error(
+        ErrorCode::GameAlreadyStarted,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
+      return;
+    }
     Err(_) => {
-      let message =
-        serde_json::to_string(&ServerMessage::Error("Cannot start the game".into())).unwrap();
-      send_message(message.as_str(), state.sessions, &msg.player_id);
+      send_error(
+        ErrorCode::StorageFailure,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
       return;
     }
   };
 
   game.players = fill_with_bots(game.players);
+  if let Some(position) = game.position.clone() {
+    game.set_up(&position);
+  }
   let update_res = database::update_game_state(&state.db, &msg.room_id, &game).await;
 
-  let game = match update_res {
+  let mut game = match update_res {
     Ok(game) => game,
     Err(_) => {
-      let message =
-        serde_json::to_string(&ServerMessage::Error("Cannot update the game".into())).unwrap();
-      send_message(message.as_str(), state.sessions, &msg.player_id);
+      send_error(
+        ErrorCode::StorageFailure,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
       return;
     }
   };
 
-  let message = serde_json::to_string(&ServerMessage::GameStarted(game)).unwrap();
+  metrics().games_started.inc();
+  state.refresh_activity(&msg.room_id);
+  let message = ServerMessage::GameStarted(game.clone());
 
-  send_message_to_room(message.as_str(), state.sessions, state.rooms, &msg.room_id);
+  send_message_to_room(
+    &message,
+    state.sessions.clone(),
+    state.rooms.clone(),
+    &msg.room_id,
+  );
+  // a set up position may hand the first turn to a bot
+  if game.is_current_player_ai() {
+    move_bot(state, &msg, &mut game, 0).await;
+  }
 }
@@ -0,0 +1,37 @@
+use super::super::actor::GameServerState;
+use crate::{
+  components::{
+    game::database,
+    game_server::utils::{send_error, send_message},
+  },
+  models::actor_messages::ClientActorMessage,
+  utils::enums::{ErrorCode, ServerMessage},
+};
+
+/// sends the whole game to a client which missed some deltas
+pub async fn sync_game(state: GameServerState, msg: ClientActorMessage) {
+  let game = match database::find_game(&state.db, &msg.room_id).await {
+    Ok(Some(game)) => game,
+    Ok(None) => {
+      send_error(
+        ErrorCode::GameNotFound,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
+      return;
+    }
+    Err(_) => {
+      send_error(
+        ErrorCode::StorageFailure,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
+      return;
+    }
+  };
+
+  let message = ServerMessage::GameUpdate(game);
+  send_message(&message, state.sessions, &msg.player_id);
+}
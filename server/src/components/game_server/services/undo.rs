@@ -0,0 +1,232 @@
+use super::super::actor::GameServerState;
+use crate::components::game_server::services::utils::{broadcast_game_state, send_legal_moves};
+use crate::{
+  components::{
+    game::database,
+    game_server::utils::{send_error, send_message_to_room},
+  },
+  models::{actor_messages::ClientActorMessage, game::Game, snapshot::Snapshot},
+  utils::enums::{ErrorCode, ServerMessage},
+};
+
+/// keeps the game from before a move of `msg.player_id`, `sequence` is the one of the game after it
+pub async fn remember_move(
+  state: &GameServerState,
+  msg: &ClientActorMessage,
+  previous: Game,
+  sequence: u64,
+) {
+  let snapshot = Snapshot {
+    game_id: msg.room_id.clone(),
+    player_id: msg.player_id.clone(),
+    sequence,
+    game: previous,
+    undo_requested: false,
+    accepted: Vec::new(),
+  };
+  // without the snapshot the move just can't be taken back
+  let _ = database::save_snapshot(&state.db, &snapshot).await;
+}
+
+/// connected human players other than the one who made the move, they have to agree with the undo
+fn players_to_ask(
+  state: &GameServerState,
+  msg: &ClientActorMessage,
+  game: &Game,
+  mover: &str,
+) -> Vec<String> {
+  let connected = state.rooms.get(&msg.room_id);
+  game
+    .players
+    .iter()
+    .filter(|player| !player.is_bot && player.id != mover)
+    .filter(|player| connected.is_some_and(|sessions| sessions.contains(&player.id)))
+    .map(|player| player.id.clone())
+    .collect()
+}
+
+fn broadcast(state: &GameServerState, msg: &ClientActorMessage, message: &ServerMessage) {
+  send_message_to_room(
+    message,
+    state.sessions.clone(),
+    state.rooms.clone(),
+    &msg.room_id,
+  );
+}
+
+/// finds the game and the snapshot of its last move, the error is sent to the player otherwise
+async fn find_last_move(
+  state: &GameServerState,
+  msg: &ClientActorMessage,
+) -> Option<(Game, Snapshot)> {
+  let game = match database::find_game(&state.db, &msg.room_id).await {
+    Ok(Some(game)) => game,
+    _ => {
+      send_error(
+        ErrorCode::GameNotFound,
+        msg.request_id.clone(),
+        state.sessions.clone(),
+        &msg.player_id,
+      );
+      return None;
+    }
+  };
+  match database::find_snapshot(&state.db, &msg.room_id).await {
+    // a finished game stays finished, later moves can't be taken back with the snapshot
+    Ok(Some(snapshot)) if snapshot.sequence == game.sequence && game.winner.is_none() => {
+      Some((game, snapshot))
+    }
+    _ => {
+      send_error(
+        ErrorCode::NothingToUndo,
+        msg.request_id.clone(),
+        state.sessions.clone(),
+        &msg.player_id,
+      );
+      None
+    }
+  }
+}
+
+/// asks the other players to take back the last move, bot-only games take it back right away
+pub async fn request_undo(state: GameServerState, msg: ClientActorMessage) {
+  let (game, snapshot) = match find_last_move(&state, &msg).await {
+    Some(last_move) => last_move,
+    None => return,
+  };
+  if snapshot.player_id != msg.player_id {
+    send_error(
+      ErrorCode::NothingToUndo,
+      msg.request_id.clone(),
+      state.sessions,
+      &msg.player_id,
+    );
+    return;
+  }
+
+  if players_to_ask(&state, &msg, &game, &snapshot.player_id).is_empty() {
+    take_back(&state, &msg, snapshot).await;
+    return;
+  }
+
+  match database::request_undo(&state.db, &msg.room_id, snapshot.sequence).await {
+    Ok(Some(_)) => {
+      let color = game
+        .get_player_by_id(&msg.player_id)
+        .map(|player| player.color);
+      if let Some(color) = color {
+        broadcast(&state, &msg, &ServerMessage::UndoRequested(color));
+      }
+    }
+    // the undo is already waiting for the answers
+    Ok(None) => send_error(
+      ErrorCode::InvalidRequest,
+      msg.request_id.clone(),
+      state.sessions,
+      &msg.player_id,
+    ),
+    Err(_) => send_error(
+      ErrorCode::StorageFailure,
+      msg.request_id.clone(),
+      state.sessions,
+      &msg.player_id,
+    ),
+  }
+}
+
+/// the move is taken back once every asked player agrees, a single refusal cancels the undo
+pub async fn answer_undo(state: GameServerState, msg: ClientActorMessage, accepted: bool) {
+  let (game, snapshot) = match find_last_move(&state, &msg).await {
+    Some(last_move) => last_move,
+    None => return,
+  };
+  let asked = players_to_ask(&state, &msg, &game, &snapshot.player_id);
+  if !snapshot.undo_requested || !asked.contains(&msg.player_id) {
+    send_error(
+      ErrorCode::InvalidRequest,
+      msg.request_id.clone(),
+      state.sessions,
+      &msg.player_id,
+    );
+    return;
+  }
+
+  if !accepted {
+    // the move can't be requested again, otherwise the players would be asked over and over
+    if database::delete_snapshot(&state.db, &msg.room_id)
+      .await
+      .is_err()
+    {
+      send_error(
+        ErrorCode::StorageFailure,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
+      return;
+    }
+    broadcast(&state, &msg, &ServerMessage::UndoResolved(false));
+    return;
+  }
+
+  let snapshot = match database::accept_undo(&state.db, &msg.room_id, &msg.player_id).await {
+    Ok(Some(snapshot)) => snapshot,
+    _ => {
+      send_error(
+        ErrorCode::StorageFailure,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
+      return;
+    }
+  };
+  if asked
+    .iter()
+    .all(|player_id| snapshot.accepted.contains(player_id))
+  {
+    take_back(&state, &msg, snapshot).await;
+  }
+}
+
+/// restores the game from before the move, the player moves again with the same dice
+async fn take_back(state: &GameServerState, msg: &ClientActorMessage, snapshot: Snapshot) {
+  if database::delete_snapshot(&state.db, &msg.room_id)
+    .await
+    .is_err()
+  {
+    send_error(
+      ErrorCode::StorageFailure,
+      msg.request_id.clone(),
+      state.sessions.clone(),
+      &msg.player_id,
+    );
+    return;
+  }
+  // the restored game replaces the one right after the move
+  let restored = Game {
+    sequence: snapshot.sequence,
+    ..snapshot.game
+  };
+  let game = match broadcast_game_state(state, &msg.room_id, &restored).await {
+    Ok(game) => game,
+    Err(_) => {
+      send_error(
+        ErrorCode::StorageFailure,
+        msg.request_id.clone(),
+        state.sessions.clone(),
+        &msg.player_id,
+      );
+      return;
+    }
+  };
+  broadcast(state, msg, &ServerMessage::UndoResolved(true));
+
+  let mover = ClientActorMessage {
+    message: msg.message.clone(),
+    room_id: msg.room_id.clone(),
+    player_id: snapshot.player_id,
+    request_id: None,
+  };
+  send_legal_moves(state, &mover, &game);
+}
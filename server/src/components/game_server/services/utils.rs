@@ -1,9 +1,11 @@
-use crate::components::game::database;
+use crate::components::game::database::{self, OutdatedGame};
 use crate::components::game_server::actor::GameServerState;
-use crate::components::game_server::utils::{send_message_to_room, send_message};
+use crate::components::game_server::utils::{send_message_to_room, send_message, send_error};
 use crate::models::actor_messages::ClientActorMessage;
-use crate::models::game::Game;
-use crate::utils::enums::ServerMessage;
+use crate::models::{game::Game, game_delta::GameDelta};
+use crate::utils::enums::{ErrorCode, ServerMessage};
+use crate::utils::metrics::metrics;
+use tracing::debug;
 
 /// updates game, sends SkipPlayer message and GameUpdate message to room,
 pub async fn skip_player(
@@ -14,9 +16,8 @@ pub async fn skip_player(
   game.update_current_player();
   game.dice_throws.clear();
 
-  let skip_message = serde_json::to_string(&ServerMessage::SkipPlayer).unwrap();
   send_message_to_room(
-    skip_message.as_str(),
+    &ServerMessage::SkipPlayer,
     state.sessions.clone(),
     state.rooms.clone(),
     &msg.room_id,
@@ -25,44 +26,78 @@ pub async fn skip_player(
   send_game_update_message(state.clone(), msg, game).await
 }
 
+/// sends the moves the current player can make with the thrown dice, per die if they are split
+pub fn send_legal_moves(state: &GameServerState, msg: &ClientActorMessage, game: &Game) {
+  let message = match game.ruleset.split_dice {
+    true => ServerMessage::LegalMovesPerDie(game.legal_moves_per_die()),
+    false => ServerMessage::LegalMoves(game.legal_moves(game.dice_throws.iter().sum())),
+  };
+  send_message(&message, state.sessions.clone(), &msg.player_id);
+}
+
 pub async fn send_roll_message(
   state: GameServerState,
   msg: &ClientActorMessage,
   roll: usize,
   can_roll_again: bool,
 ) {
-  let roll_message =
-    serde_json::to_string(&ServerMessage::DiceValue(roll, can_roll_again)).unwrap();
   send_message_to_room(
-    roll_message.as_str(),
+    &ServerMessage::DiceValue(roll, can_roll_again),
     state.sessions.clone(),
     state.rooms,
     &msg.room_id,
   );
 }
 
+/// saves the game and sends the room what changed since the previous broadcast,
+/// the whole game is sent only if the changes can't be described by a `GameDelta`
+pub async fn broadcast_game_state(
+  state: &GameServerState,
+  room_id: &str,
+  game: &Game,
+) -> anyhow::Result<Game> {
+  let (previous, current) = database::advance_game_state(&state.db, room_id, game).await?;
+  if previous.winner.is_none() && current.winner.is_some() {
+    metrics().games_finished.inc();
+  }
+  // the human on turn gets the whole timeout after every change, no matter how long
+  // the bots before them played
+  if !current.is_current_player_ai() {
+    state.refresh_activity(room_id);
+  }
+  let message = match GameDelta::between(&previous, &current) {
+    Some(delta) => ServerMessage::GameDelta(delta),
+    None => ServerMessage::GameUpdate(current.clone()),
+  };
+
+  send_message_to_room(
+    &message,
+    state.sessions.clone(),
+    state.rooms.clone(),
+    room_id,
+  );
+  Ok(current)
+}
+
 pub async fn send_game_update_message(
   state: GameServerState,
   msg: &ClientActorMessage,
   game: &Game,
 ) -> Game {
-  return match database::update_game_state(&state.db, &msg.room_id, game)
-      .await {
-    Ok(game) => {
-      let update_message = serde_json::to_string(&ServerMessage::GameUpdate(game.clone())).unwrap();
-
-      send_message_to_room(
-        update_message.as_str(),
-        state.sessions.clone(),
-        state.rooms.clone(),
-        &msg.room_id,
-      );
-      game
+  return match broadcast_game_state(&state, &msg.room_id, game).await {
+    Ok(game) => game,
+    // the outdated copy is returned, bots stop once they see it's outdated
+    Err(e) if e.is::<OutdatedGame>() => {
+      debug!("the game changed in the meantime, it isn't saved");
+      game.clone()
     }
     Err(_) => {
-      let message =
-          serde_json::to_string(&ServerMessage::Error("Cannot update the game".into())).unwrap();
-      send_message(message.as_str(), state.sessions, &msg.player_id);
+      send_error(
+        ErrorCode::StorageFailure,
+        msg.request_id.clone(),
+        state.sessions,
+        &msg.player_id,
+      );
       game.clone()
     }
   }
@@ -1,25 +1,60 @@
 use actix::Recipient;
 use std::collections::{HashMap, HashSet};
+use tracing::{debug, error};
 
-use crate::models::actor_messages::WsMessage;
+use crate::models::{
+  actor_messages::WsMessage, server_error::ServerError, wire_format::EncodedMessage,
+};
+use crate::utils::enums::{ErrorCode, ServerMessage};
+use crate::utils::metrics::metrics;
 
-pub fn send_message(message: &str, sessions: HashMap<String, Recipient<WsMessage>>, id_to: &str) {
+pub fn send_message(
+  message: &ServerMessage,
+  sessions: HashMap<String, Recipient<WsMessage>>,
+  id_to: &str,
+) {
+  send_encoded(&EncodedMessage::new(message), &sessions, id_to);
+}
+
+fn send_encoded(
+  message: &EncodedMessage,
+  sessions: &HashMap<String, Recipient<WsMessage>>,
+  id_to: &str,
+) {
   if let Some(session) = sessions.get(id_to) {
-    session.do_send(WsMessage(message.to_owned()));
+    session.do_send(WsMessage(message.clone()));
   } else {
-    println!("attempting to send message but couldn't find session with given id.");
+    debug!(player_id = id_to, "no session to send the message to");
+  }
+}
+
+/// `request_id` is the id of the client request which caused the error, if there is one
+pub fn send_error(
+  code: ErrorCode,
+  request_id: Option<String>,
+  sessions: HashMap<String, Recipient<WsMessage>>,
+  id_to: &str,
+) {
+  match code {
+    ErrorCode::StorageFailure => error!(player_id = id_to, "couldn't access the database"),
+    _ => debug!(player_id = id_to, ?code, "sending error"),
   }
+  metrics().count_error(&code);
+  let error = ServerMessage::Error(ServerError::new(code, request_id));
+  send_message(&error, sessions, id_to);
 }
 
+/// the message is encoded once for the whole room
 pub fn send_message_to_room(
-  message: &str,
+  message: &ServerMessage,
   sessions: HashMap<String, Recipient<WsMessage>>,
   rooms: HashMap<String, HashSet<String>>,
   room_id: &str,
 ) {
   if let Some(sessions_ids) = rooms.get(room_id) {
+    let message = EncodedMessage::new(message);
     for session_id in sessions_ids {
-      send_message(message, sessions.clone(), session_id);
+      send_encoded(&message, &sessions, session_id);
     }
   }
 }
@@ -0,0 +1,151 @@
+use actix::prelude::{Actor, AsyncContext, Context, Handler, Recipient};
+use mongodb::Database;
+use std::{
+  collections::HashMap,
+  sync::Arc,
+  time::{Duration, Instant},
+};
+use tokio::sync::Mutex;
+use tracing::{info_span, Instrument};
+
+use super::services::create_match::create_match;
+use crate::models::server_error::ServerError;
+use crate::models::{
+  actor_messages::{JoinQueue, LeaveQueue, WsMessage},
+  queue_preferences::QueuePreferences,
+  wire_format::EncodedMessage,
+};
+use crate::utils::enums::{ErrorCode, ServerMessage};
+use crate::utils::metrics::metrics;
+
+type Session = Recipient<WsMessage>;
+
+/// how long the first player of a group waits before the remaining seats are filled with bots
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(30);
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct QueuedPlayer {
+  pub ticket_id: String,
+  pub name: String,
+  pub address: Session,
+}
+
+struct WaitingGroup {
+  players: Vec<QueuedPlayer>,
+  since: Instant,
+}
+
+/// Matchmaker actor which groups players waiting for a quick match by their preferences
+/// and creates a game once a group is full or has waited long enough
+pub struct Matchmaker {
+  db: Arc<Mutex<Database>>,
+  groups: HashMap<QueuePreferences, WaitingGroup>,
+}
+
+impl Matchmaker {
+  pub fn new(db: Arc<Mutex<Database>>) -> Self {
+    Matchmaker {
+      db,
+      groups: HashMap::new(),
+    }
+  }
+
+  fn start_match(&mut self, preferences: &QueuePreferences) {
+    if let Some(group) = self.groups.remove(preferences) {
+      let db = self.db.clone();
+      let ruleset = preferences.ruleset.clone();
+      let span =
+        info_span!("match", players = group.players.len(), waited = ?group.since.elapsed());
+      actix_web::rt::spawn(create_match(db, group.players, ruleset).instrument(span));
+    }
+  }
+
+  fn send_queue_update(&self, preferences: &QueuePreferences) {
+    if let Some(group) = self.groups.get(preferences) {
+      let message = EncodedMessage::new(&ServerMessage::QueueUpdate(group.players.len()));
+      for player in &group.players {
+        player.address.do_send(WsMessage(message.clone()));
+      }
+    }
+  }
+
+  /// groups which have been waiting for too long are started with bots
+  fn check_timeouts(&mut self) {
+    let expired: Vec<QueuePreferences> = self
+      .groups
+      .iter()
+      .filter(|(_, group)| group.since.elapsed() >= QUEUE_TIMEOUT)
+      .map(|(preferences, _)| preferences.clone())
+      .collect();
+
+    for preferences in expired {
+      self.start_match(&preferences);
+    }
+  }
+}
+
+impl Actor for Matchmaker {
+  type Context = Context<Self>;
+
+  fn started(&mut self, ctx: &mut Self::Context) {
+    ctx.run_interval(CHECK_INTERVAL, |act, _| act.check_timeouts());
+  }
+}
+
+impl Handler<JoinQueue> for Matchmaker {
+  type Result = ();
+
+  fn handle(&mut self, msg: JoinQueue, _: &mut Context<Self>) {
+    if !(1..=4).contains(&msg.preferences.player_count) {
+      metrics().count_error(&ErrorCode::InvalidRequest);
+      let message = EncodedMessage::new(&ServerMessage::Error(ServerError::new(
+        ErrorCode::InvalidRequest,
+        None,
+      )));
+      msg.address.do_send(WsMessage(message));
+      return;
+    }
+
+    let preferences = msg.preferences.clone();
+    let group = self
+      .groups
+      .entry(preferences.clone())
+      .or_insert_with(|| WaitingGroup {
+        players: Vec::new(),
+        since: Instant::now(),
+      });
+    group.players.push(QueuedPlayer {
+      ticket_id: msg.ticket_id,
+      name: msg.name,
+      address: msg.address,
+    });
+
+    if group.players.len() >= preferences.player_count {
+      self.start_match(&preferences);
+    } else {
+      self.send_queue_update(&preferences);
+    }
+  }
+}
+
+impl Handler<LeaveQueue> for Matchmaker {
+  type Result = ();
+
+  fn handle(&mut self, msg: LeaveQueue, _: &mut Context<Self>) {
+    let mut changed: Vec<QueuePreferences> = Vec::new();
+    for (preferences, group) in &mut self.groups {
+      let count = group.players.len();
+      group
+        .players
+        .retain(|player| player.ticket_id != msg.ticket_id);
+      if group.players.len() != count {
+        changed.push(preferences.clone());
+      }
+    }
+    self.groups.retain(|_, group| !group.players.is_empty());
+
+    for preferences in changed {
+      self.send_queue_update(&preferences);
+    }
+  }
+}
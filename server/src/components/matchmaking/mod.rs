@@ -0,0 +1,3 @@
+pub mod actor;
+pub mod services;
+pub mod session;
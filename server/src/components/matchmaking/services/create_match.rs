@@ -0,0 +1,63 @@
+use mongodb::Database;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::{
+  components::{game::database, matchmaking::actor::QueuedPlayer},
+  models::{
+    actor_messages::WsMessage, color::Color, game::Game, player::Player, ruleset::Ruleset,
+    server_error::ServerError, wire_format::EncodedMessage,
+  },
+  utils::{
+    enums::{ErrorCode, ServerMessage},
+    game::fill_with_bots,
+    metrics::metrics,
+  },
+};
+
+/// creates an already started game for the queued players (remaining seats are taken by bots)
+/// and lets every player know where to connect
+pub async fn create_match(db: Arc<Mutex<Database>>, queued: Vec<QueuedPlayer>, ruleset: Ruleset) {
+  let players: Vec<(Player, QueuedPlayer)> = queued
+    .into_iter()
+    .zip(Color::ordered())
+    .map(|(queued_player, color)| {
+      let player_id = Uuid::new_v4().to_string();
+      let player = Player::new(player_id, queued_player.name.clone(), color, false);
+      (player, queued_player)
+    })
+    .collect();
+
+  let mut game = Game::new();
+  game.ruleset = ruleset;
+  game.started = true;
+  game.players = fill_with_bots(players.iter().map(|(player, _)| player.clone()).collect());
+
+  let game_id = match database::create_game(&db, game).await {
+    Ok(game_id) => game_id,
+    Err(e) => {
+      error!(error = %e, "couldn't create the match");
+      metrics().count_error(&ErrorCode::StorageFailure);
+      let message = EncodedMessage::new(&ServerMessage::Error(ServerError::new(
+        ErrorCode::StorageFailure,
+        None,
+      )));
+      for (_, queued_player) in players {
+        queued_player.address.do_send(WsMessage(message.clone()));
+      }
+      return;
+    }
+  };
+
+  metrics().games_started.inc();
+  info!(room_id = %game_id, "match created");
+  for (player, queued_player) in players {
+    let message = EncodedMessage::new(&ServerMessage::MatchFound {
+      game_id: game_id.clone(),
+      player_id: player.id,
+    });
+    queued_player.address.do_send(WsMessage(message));
+  }
+}
@@ -0,0 +1,122 @@
+use actix::{Actor, ActorContext, Addr, AsyncContext, Handler, Running, StreamHandler};
+use actix_web_actors::ws;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use super::actor::Matchmaker;
+use crate::models::server_error::ServerError;
+use crate::models::{
+  actor_messages::{JoinQueue, LeaveQueue, WsMessage},
+  messages::QueueRequest,
+  queue_preferences::QueuePreferences,
+  wire_format::EncodedMessage,
+};
+use crate::utils::enums::{ErrorCode, ServerMessage};
+use crate::utils::metrics::metrics;
+
+/// how often the waiting client is pinged
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+/// a client which doesn't answer for this long is disconnected and leaves the queue
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Matchmaking session actor (for each client waiting for a quick match)
+/// The client sends a `QueueRequest` first, afterwards it only listens for `MatchFound`
+pub struct MatchmakingSession {
+  ticket_id: String,
+  matchmaker: Addr<Matchmaker>,
+  queued: bool,
+  heartbeat: Instant,
+}
+
+impl MatchmakingSession {
+  pub fn new(ticket_id: String, matchmaker: Addr<Matchmaker>) -> Self {
+    MatchmakingSession {
+      ticket_id,
+      matchmaker,
+      queued: false,
+      heartbeat: Instant::now(),
+    }
+  }
+
+  fn join_queue(&mut self, text: &str, ctx: &mut ws::WebsocketContext<Self>) {
+    let request = match serde_json::from_str::<QueueRequest>(text) {
+      Ok(request) => request,
+      Err(_) => {
+        metrics().count_error(&ErrorCode::InvalidRequest);
+        let message = EncodedMessage::new(&ServerMessage::Error(ServerError::new(
+          ErrorCode::InvalidRequest,
+          None,
+        )));
+        metrics().count_out(&message);
+        ctx.text(message.json.as_ref());
+        return;
+      }
+    };
+
+    self.queued = true;
+    self.matchmaker.do_send(JoinQueue {
+      address: ctx.address().recipient(),
+      ticket_id: self.ticket_id.clone(),
+      name: request.name,
+      preferences: QueuePreferences {
+        player_count: request.player_count,
+        ruleset: request.ruleset,
+      },
+    });
+  }
+}
+
+impl Actor for MatchmakingSession {
+  type Context = ws::WebsocketContext<Self>;
+
+  fn started(&mut self, ctx: &mut Self::Context) {
+    ctx.run_interval(HEARTBEAT_INTERVAL, |act, ctx| {
+      if act.heartbeat.elapsed() > CLIENT_TIMEOUT {
+        debug!(ticket_id = %act.ticket_id, "matchmaking client timed out");
+        ctx.stop();
+        return;
+      }
+      ctx.ping(b"");
+    });
+  }
+
+  fn stopping(&mut self, _: &mut Self::Context) -> Running {
+    self.matchmaker.do_send(LeaveQueue {
+      ticket_id: self.ticket_id.clone(),
+    });
+    Running::Stop
+  }
+}
+
+/// Handler for messages coming from the client
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for MatchmakingSession {
+  fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+    match msg {
+      Ok(ws::Message::Ping(msg)) => {
+        self.heartbeat = Instant::now();
+        ctx.pong(&msg);
+      }
+      Ok(ws::Message::Pong(_msg)) => {
+        self.heartbeat = Instant::now();
+      }
+      // the player can only queue once per connection
+      Ok(ws::Message::Text(s)) if !self.queued => self.join_queue(&s, ctx),
+      Ok(ws::Message::Close(reason)) => {
+        ctx.close(reason);
+        ctx.stop();
+      }
+      Ok(_) => {}
+      Err(_) => ctx.stop(),
+    }
+  }
+}
+
+// Sending a message back to the client from the session actor
+impl Handler<WsMessage> for MatchmakingSession {
+  type Result = ();
+
+  fn handle(&mut self, msg: WsMessage, ctx: &mut Self::Context) {
+    metrics().count_out(&msg.0);
+    ctx.text(msg.0.json.as_ref());
+  }
+}
@@ -0,0 +1,11 @@
+use actix_web::{get, HttpResponse};
+
+use crate::utils::metrics::metrics;
+
+/// load of the server in the Prometheus text format
+#[get("/metrics")]
+pub async fn get_metrics() -> HttpResponse {
+  HttpResponse::Ok()
+    .content_type("text/plain; version=0.0.4; charset=utf-8")
+    .body(metrics().render())
+}
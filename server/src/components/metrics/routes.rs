@@ -0,0 +1,7 @@
+use actix_web::web;
+
+use super::controller;
+
+pub fn attach_routes(config: &mut web::ServiceConfig) {
+  config.service(controller::get_metrics);
+}
@@ -1,3 +1,6 @@
+pub mod client_bundle;
 pub mod game;
 pub mod game_server;
+pub mod matchmaking;
+pub mod metrics;
 pub mod session;
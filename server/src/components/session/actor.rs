@@ -2,11 +2,24 @@ use actix::{
   fut, Actor, ActorContext, ActorFutureExt, Addr, AsyncContext, ContextFutureSpawner, Handler,
   Running, StreamHandler, WrapFuture,
 };
-use actix_web_actors::ws;
+use actix_web_actors::ws::{self, CloseCode, CloseReason};
 use std::time::Instant;
+use tracing::{debug, info_span, warn, Span};
 
 use crate::components::game_server::actor::GameServer;
-use crate::models::actor_messages::{ClientActorMessage, Connect, Disconnect, WsMessage};
+use crate::models::{
+  actor_messages::{ClientActorMessage, Connect, Disconnect, WsMessage},
+  server_error::ServerError,
+  wire_format::{EncodedMessage, WireFormat},
+};
+use crate::utils::metrics::metrics;
+use crate::utils::{
+  enums::{ClientMessage, ErrorCode, ServerMessage},
+  protocol::{parse_binary_client_request, parse_client_request, MAX_MESSAGE_SIZE},
+};
+
+/// the connection is closed once the client sends this many invalid messages
+const MAX_PROTOCOL_VIOLATIONS: usize = 5;
 
 /// Game session actor (for each connected client)
 /// Sends messages to the GameServer actor who coordinates all connected sessions
@@ -15,16 +28,106 @@ pub struct GameSession {
   room: String,
   game_server: Addr<GameServer>,
   heartbeat: Instant,
+  is_spectator: bool,
+  wire_format: WireFormat,
+  protocol_violations: usize,
+  /// events of the session are recorded with its room and player
+  span: Span,
 }
 
 impl GameSession {
-  pub fn new(id: String, room: String, game_server: Addr<GameServer>) -> Self {
-    println!("created game session");
+  pub fn new(
+    id: String,
+    room: String,
+    game_server: Addr<GameServer>,
+    is_spectator: bool,
+    wire_format: WireFormat,
+  ) -> Self {
+    let span = info_span!("session", room_id = %room, player_id = %id, spectator = is_spectator);
+    debug!(parent: &span, ?wire_format, "created game session");
     GameSession {
+      span,
       id,
       room,
       heartbeat: Instant::now(),
       game_server,
+      is_spectator,
+      wire_format,
+      protocol_violations: 0,
+    }
+  }
+
+  /// messages are already encoded by the GameServer, the session picks its wire format
+  fn send(&self, message: &EncodedMessage, ctx: &mut ws::WebsocketContext<Self>) {
+    metrics().count_out(message);
+    if self.wire_format == WireFormat::MessagePack {
+      match &message.message_pack {
+        Some(bytes) => return ctx.binary(bytes.clone()),
+        None => warn!(parent: &self.span, "couldn't encode message as MessagePack"),
+      }
+    }
+    ctx.text(message.json.as_ref());
+  }
+
+  fn handle_request(
+    &mut self,
+    request: Result<(ClientMessage, Option<String>), ErrorCode>,
+    ctx: &mut ws::WebsocketContext<Self>,
+  ) {
+    match request {
+      Ok((message, request_id)) => {
+        metrics().count_in(&message);
+        self.game_server.do_send(ClientActorMessage {
+          player_id: self.id.clone(),
+          message,
+          room_id: self.room.clone(),
+          request_id,
+        })
+      }
+      Err(code) => self.reject(code, ctx),
+    }
+  }
+
+  /// the GameServer didn't accept the session, the client learns why before it is closed
+  fn refuse(&mut self, code: ErrorCode, ctx: &mut ws::WebsocketContext<Self>) {
+    let description = code.to_string();
+    metrics().count_error(&code);
+    let error = ServerMessage::Error(ServerError::new(code, None));
+    self.send(&EncodedMessage::new(&error), ctx);
+    ctx.close(Some(CloseReason {
+      code: CloseCode::Again,
+      description: Some(description),
+    }));
+    ctx.stop();
+  }
+
+  /// answers an invalid message with a protocol error, repeated violations close the connection
+  fn reject(&mut self, code: ErrorCode, ctx: &mut ws::WebsocketContext<Self>) {
+    self.protocol_violations += 1;
+    warn!(
+      parent: &self.span,
+      ?code,
+      violations = self.protocol_violations,
+      max_violations = MAX_PROTOCOL_VIOLATIONS,
+      "protocol error"
+    );
+
+    let close_code = match code {
+      ErrorCode::MessageTooLarge { .. } => CloseCode::Size,
+      ErrorCode::UnsupportedFrame => CloseCode::Unsupported,
+      _ => CloseCode::Policy,
+    };
+    metrics().count_error(&code);
+    let error = ServerMessage::Error(ServerError::new(code, None));
+    self.send(&EncodedMessage::new(&error), ctx);
+
+    if self.protocol_violations >= MAX_PROTOCOL_VIOLATIONS {
+      warn!(parent: &self.span, "closing connection, too many invalid messages");
+      ctx.close(Some(CloseReason {
+        code: close_code,
+        description: Some("Too many invalid messages".into()),
+      }));
+      ctx.stop();
     }
   }
 }
@@ -34,7 +137,7 @@ impl Actor for GameSession {
   type Context = ws::WebsocketContext<Self>;
 
   fn started(&mut self, ctx: &mut Self::Context) {
-    println!("session started");
+    debug!(parent: &self.span, "session started");
 
     let address = ctx.address();
     self
@@ -43,11 +146,14 @@ impl Actor for GameSession {
         address: address.recipient(),
         player_id: self.id.clone(),
         room_id: self.room.clone(),
+        is_spectator: self.is_spectator,
       })
       .into_actor(self)
-      .then(|res, _, ctx| {
-        if res.is_err() {
-          ctx.stop();
+      .then(|res, act, ctx| {
+        match res {
+          Ok(Ok(())) => {}
+          Ok(Err(code)) => act.refuse(code, ctx),
+          Err(_) => ctx.stop(),
         }
         fut::ready(())
       })
@@ -55,7 +161,7 @@ impl Actor for GameSession {
   }
 
   fn stopping(&mut self, _: &mut Self::Context) -> Running {
-    println!("stoppping");
+    debug!(parent: &self.span, "session stopping");
     self.game_server.do_send(Disconnect {
       room_id: self.room.clone(),
       player_id: self.id.clone(),
@@ -76,7 +182,10 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for GameSession {
       Ok(ws::Message::Pong(_msg)) => {
         self.heartbeat = Instant::now();
       }
-      Ok(ws::Message::Binary(bin)) => ctx.binary(bin),
+      Ok(ws::Message::Binary(bytes)) => match self.wire_format {
+        WireFormat::MessagePack => self.handle_request(parse_binary_client_request(&bytes), ctx),
+        WireFormat::Json => self.reject(ErrorCode::UnsupportedFrame, ctx),
+      },
       Ok(ws::Message::Close(reason)) => {
         ctx.close(reason);
         ctx.stop();
@@ -84,15 +193,21 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for GameSession {
       Ok(ws::Message::Continuation(_)) => {
         // message is too large so its sent in continuation
         // we are not handling large data
-        ctx.stop();
+        self.reject(
+          ErrorCode::MessageTooLarge {
+            max_size: MAX_MESSAGE_SIZE,
+          },
+          ctx,
+        );
       }
       Ok(ws::Message::Nop) => {}
-      Ok(ws::Message::Text(s)) => self.game_server.do_send(ClientActorMessage {
-        player_id: self.id.clone(),
-        content: s.to_string(),
-        room_id: self.room.clone(),
-      }),
-      Err(e) => panic!("{}", e),
+      // text frames are accepted with any wire format, which is handy for debugging
+      Ok(ws::Message::Text(s)) => self.handle_request(parse_client_request(&s), ctx),
+      Err(e) => {
+        warn!(parent: &self.span, error = %e, "websocket error");
+        ctx.close(Some(CloseCode::Protocol.into()));
+        ctx.stop();
+      }
     }
   }
 }
@@ -102,7 +217,6 @@ impl Handler<WsMessage> for GameSession {
   type Result = ();
 
   fn handle(&mut self, msg: WsMessage, ctx: &mut Self::Context) {
-    // sending text to the client
-    ctx.text(msg.0);
+    self.send(&msg.0, ctx);
   }
 }
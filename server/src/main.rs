@@ -1,55 +1,80 @@
 use actix::Actor;
 use actix_cors::Cors;
 use actix_web::{middleware, web, App, HttpServer};
+use components::client_bundle::bundle::ClientBundle;
 use components::game_server::actor::GameServer;
+use components::matchmaking::actor::Matchmaker;
 use dotenv::dotenv;
-use env_logger::Env;
 use mongodb::{options::ClientOptions, Client};
-use std::env;
 use std::sync::Arc;
 use tokio::sync::Mutex;
+use tracing_subscriber::EnvFilter;
 
 mod components;
 mod models;
-mod types;
 mod utils;
 
 use models::app_data::AppData;
+use models::server_config::{CorsConfig, LogConfig, LogFormat, ServerConfig};
+
+/// events carry the fields of their spans (room, player, command), records of the `log`
+/// crate (actix, mongodb) are turned into events as well
+fn init_tracing(config: &LogConfig) {
+  let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.level));
+  let subscriber = tracing_subscriber::fmt().with_env_filter(filter);
+  match config.format {
+    LogFormat::Text => subscriber.init(),
+    LogFormat::Json => subscriber.json().init(),
+  }
+}
+
+fn cors(config: &CorsConfig) -> Cors {
+  let cors = Cors::default().allow_any_header().allow_any_method();
+  if config.allows_any_origin() {
+    return cors.allow_any_origin();
+  }
+  config
+    .allowed_origins
+    .iter()
+    .fold(cors, |cors, origin| cors.allowed_origin(origin))
+}
 
 #[actix_web::main]
 async fn main() -> anyhow::Result<()> {
-  env_logger::init_from_env(Env::default().default_filter_or("info"));
   dotenv().ok();
+  let config = Arc::new(ServerConfig::load()?);
+  init_tracing(&config.log);
 
-  let database_url = env::var("DATABASE_URL").expect("DATABASE_URL env variable is not set");
-  let port = env::var("PORT").unwrap_or("8080".to_string());
-  
+  // validated to be set
+  let database_url = config.storage.url.clone().unwrap_or_default();
   let mut client_options = ClientOptions::parse(database_url).await?;
-  client_options.app_name = Some("Ludo".to_string());
+  client_options.app_name = Some(config.storage.app_name.clone());
 
   let client = Client::with_options(client_options)?;
-  let db = Arc::new(Mutex::new(client.database("main")));
+  let db = Arc::new(Mutex::new(client.database(&config.storage.database)));
+  components::game::database::create_indexes(&db).await?;
 
-  let game_server_addr = GameServer::new(db.clone()).start();
+  let game_server_addr = GameServer::new(db.clone(), config.clone()).start();
+  let matchmaker_addr = Matchmaker::new(db.clone()).start();
 
   let app_data = web::Data::new(AppData {
     game_server_addr,
+    matchmaker_addr,
     db: db.clone(),
   });
 
+  let client_bundle = ClientBundle::from_config(&config.client).map(web::Data::new);
+  let cors_config = config.cors.clone();
   HttpServer::new(move || {
     App::new()
-      .wrap(
-        Cors::default()
-          .allow_any_header()
-          .allow_any_origin()
-          .allow_any_method(),
-      )
+      .wrap(cors(&cors_config))
       .app_data(app_data.clone())
       .wrap(middleware::Logger::default())
       .configure(components::game::routes::attach_routes)
+      .configure(components::metrics::routes::attach_routes)
+      .configure(|config| components::client_bundle::routes::attach_routes(config, &client_bundle))
   })
-  .bind(format!("0.0.0.0:{}",port))?
+  .bind(config.bind_address())?
   .run()
   .await?;
 
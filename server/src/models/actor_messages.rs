@@ -1,17 +1,22 @@
 use actix::{Message, Recipient};
 
+use super::{queue_preferences::QueuePreferences, wire_format::EncodedMessage};
+use crate::utils::enums::{ClientMessage, ErrorCode};
+
 // `rtype` is a return type of the message
 
 #[derive(Message)]
 #[rtype(result = "()")]
-pub struct WsMessage(pub String);
+pub struct WsMessage(pub EncodedMessage);
 
 #[derive(Message)]
-#[rtype(result = "()")]
+#[rtype(result = "Result<(), ErrorCode>")]
 pub struct Connect {
   pub address: Recipient<WsMessage>,
   pub player_id: String,
   pub room_id: String,
+  /// spectators receive all updates of the room but can't send commands
+  pub is_spectator: bool,
 }
 
 #[derive(Message)]
@@ -24,7 +29,23 @@ pub struct Disconnect {
 #[derive(Message)]
 #[rtype(result = "()")]
 pub struct ClientActorMessage {
-  pub content: String,
+  pub message: ClientMessage,
   pub room_id: String,
   pub player_id: String,
+  pub request_id: Option<String>,
+}
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct JoinQueue {
+  pub address: Recipient<WsMessage>,
+  pub ticket_id: String,
+  pub name: String,
+  pub preferences: QueuePreferences,
+}
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct LeaveQueue {
+  pub ticket_id: String,
 }
@@ -5,8 +5,10 @@ use actix::Addr;
 use mongodb::Database;
 
 use crate::components::game_server::actor::GameServer;
+use crate::components::matchmaking::actor::Matchmaker;
 
 pub struct AppData {
   pub game_server_addr: Addr<GameServer>,
+  pub matchmaker_addr: Addr<Matchmaker>,
   pub db: Arc<Mutex<Database>>,
 }
@@ -1,6 +1,11 @@
 pub mod actor_messages;
 pub mod app_data;
-pub mod color;
-pub mod game;
-pub mod player;
-pub mod position;
+pub mod queue_preferences;
+pub mod server_config;
+pub mod snapshot;
+pub mod wire_format;
+
+pub use engine::models::{
+  chat, color, game, game_delta, game_move, game_summary, messages, player, position, ruleset,
+  server_error, visibility,
+};
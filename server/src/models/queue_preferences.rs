@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+use super::ruleset::Ruleset;
+
+/// players are only matched with players who want the same kind of game
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct QueuePreferences {
+  /// number of human players, remaining seats are filled with bots
+  pub player_count: usize,
+  pub ruleset: Ruleset,
+}
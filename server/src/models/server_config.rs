@@ -0,0 +1,412 @@
+use anyhow::{bail, Context};
+use serde::Deserialize;
+use std::{env, fmt::Display, fs, io, path::Path, str::FromStr, time::Duration};
+
+/// read at startup unless `CONFIG_FILE` points to another file, the server runs without it
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+/// settings of the server, read from a TOML file and overridden by environment variables,
+/// see `config.toml` for what each of them does
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+  pub server: BindConfig,
+  pub storage: StorageConfig,
+  pub cors: CorsConfig,
+  pub bots: BotConfig,
+  pub turns: TurnConfig,
+  pub rooms: RoomConfig,
+  pub log: LogConfig,
+  pub client: ClientConfig,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct BindConfig {
+  pub host: String,
+  pub port: u16,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+pub enum StorageBackend {
+  #[serde(rename = "mongodb")]
+  MongoDb,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+  pub backend: StorageBackend,
+  /// usually comes from `DATABASE_URL`, so the credentials stay out of the file
+  pub url: Option<String>,
+  pub database: String,
+  pub app_name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+  /// `"*"` allows requests from any origin
+  pub allowed_origins: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct BotConfig {
+  /// how long a bot waits before its throw once the previous move has been animated
+  pub delay_ms: u64,
+  /// clients step a moved pawn by one field per step, see the client's game reducer
+  pub animation_step_ms: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TurnConfig {
+  /// a human on turn is skipped once the game hasn't changed for this long,
+  /// turns never time out if it's missing
+  pub timeout_secs: Option<u64>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RoomConfig {
+  pub max_spectators: usize,
+  pub max_chat_length: usize,
+  /// at most `chat_rate_limit` chat messages (including emotes) per `chat_rate_window_secs`
+  pub chat_rate_limit: usize,
+  pub chat_rate_window_secs: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+  Text,
+  Json,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+  pub format: LogFormat,
+  /// used unless `RUST_LOG` is set
+  pub level: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientConfig {
+  /// the Trunk build of the client (`client/dist`), it's served next to the api
+  pub static_dir: Option<String>,
+  /// serves the bundle compiled into the binary with the `embed-client` feature instead
+  pub embedded: bool,
+}
+
+impl Default for BindConfig {
+  fn default() -> Self {
+    BindConfig {
+      host: "0.0.0.0".to_string(),
+      port: 8080,
+    }
+  }
+}
+
+impl Default for StorageConfig {
+  fn default() -> Self {
+    StorageConfig {
+      backend: StorageBackend::MongoDb,
+      url: None,
+      database: "main".to_string(),
+      app_name: "Ludo".to_string(),
+    }
+  }
+}
+
+impl Default for CorsConfig {
+  fn default() -> Self {
+    CorsConfig {
+      allowed_origins: vec!["*".to_string()],
+    }
+  }
+}
+
+impl Default for BotConfig {
+  fn default() -> Self {
+    BotConfig {
+      delay_ms: 1000,
+      animation_step_ms: 250,
+    }
+  }
+}
+
+impl Default for RoomConfig {
+  fn default() -> Self {
+    RoomConfig {
+      max_spectators: 50,
+      max_chat_length: 200,
+      chat_rate_limit: 5,
+      chat_rate_window_secs: 10,
+    }
+  }
+}
+
+impl Default for LogConfig {
+  fn default() -> Self {
+    LogConfig {
+      format: LogFormat::Text,
+      level: "info".to_string(),
+    }
+  }
+}
+
+impl FromStr for LogFormat {
+  type Err = anyhow::Error;
+
+  fn from_str(format: &str) -> anyhow::Result<Self> {
+    match format {
+      "text" => Ok(LogFormat::Text),
+      "json" => Ok(LogFormat::Json),
+      _ => bail!("unknown log format {}, use text or json", format),
+    }
+  }
+}
+
+impl CorsConfig {
+  pub fn allows_any_origin(&self) -> bool {
+    self.allowed_origins.iter().any(|origin| origin == "*")
+  }
+}
+
+impl BotConfig {
+  pub fn delay(&self) -> Duration {
+    Duration::from_millis(self.delay_ms)
+  }
+
+  /// time the clients need to show a move of `animation_steps` fields, see `Game::animation_steps`
+  pub fn animation_pause(&self, animation_steps: usize) -> Duration {
+    Duration::from_millis(self.animation_step_ms * animation_steps as u64)
+  }
+}
+
+impl TurnConfig {
+  pub fn timeout(&self) -> Option<Duration> {
+    self.timeout_secs.map(Duration::from_secs)
+  }
+}
+
+impl RoomConfig {
+  pub fn chat_rate_window(&self) -> Duration {
+    Duration::from_secs(self.chat_rate_window_secs)
+  }
+}
+
+fn parse_var<T: FromStr>(name: &str, value: &str) -> anyhow::Result<T>
+where
+  T::Err: Display,
+{
+  value
+    .trim()
+    .parse()
+    .map_err(|e| anyhow::anyhow!("invalid {} {:?}: {}", name, value, e))
+}
+
+impl ServerConfig {
+  /// reads the file named by `CONFIG_FILE` (or `config.toml` if it exists),
+  /// applies the environment and checks the result
+  pub fn load() -> anyhow::Result<Self> {
+    let (path, required) = match env::var("CONFIG_FILE") {
+      Ok(path) => (path, true),
+      Err(_) => (DEFAULT_CONFIG_FILE.to_string(), false),
+    };
+    let mut config = match fs::read_to_string(&path) {
+      Ok(text) => {
+        Self::from_toml(&text).with_context(|| format!("invalid config file {}", path))?
+      }
+      Err(e) if !required && e.kind() == io::ErrorKind::NotFound => Self::default(),
+      Err(e) => return Err(e).with_context(|| format!("couldn't read config file {}", path)),
+    };
+    config.apply_env(|name| env::var(name).ok())?;
+    config.validate()?;
+    Ok(config)
+  }
+
+  pub fn from_toml(text: &str) -> anyhow::Result<Self> {
+    Ok(toml::from_str(text)?)
+  }
+
+  /// environment variables win over the file, `var` looks one up by name
+  pub fn apply_env(&mut self, var: impl Fn(&str) -> Option<String>) -> anyhow::Result<()> {
+    if let Some(url) = var("DATABASE_URL") {
+      self.storage.url = Some(url);
+    }
+    if let Some(database) = var("DATABASE_NAME") {
+      self.storage.database = database;
+    }
+    if let Some(host) = var("HOST") {
+      self.server.host = host;
+    }
+    if let Some(port) = var("PORT") {
+      self.server.port = parse_var("PORT", &port)?;
+    }
+    if let Some(origins) = var("CORS_ORIGINS") {
+      self.cors.allowed_origins = origins
+        .split(',')
+        .map(|origin| origin.trim().to_string())
+        .filter(|origin| !origin.is_empty())
+        .collect();
+    }
+    if let Some(delay) = var("BOT_DELAY_MS") {
+      self.bots.delay_ms = parse_var("BOT_DELAY_MS", &delay)?;
+    }
+    if let Some(timeout) = var("TURN_TIMEOUT_SECS") {
+      // an empty value turns the timeout off
+      self.turns.timeout_secs = match timeout.trim() {
+        "" => None,
+        timeout => Some(parse_var("TURN_TIMEOUT_SECS", timeout)?),
+      };
+    }
+    if let Some(max_spectators) = var("MAX_SPECTATORS") {
+      self.rooms.max_spectators = parse_var("MAX_SPECTATORS", &max_spectators)?;
+    }
+    if let Some(format) = var("LOG_FORMAT") {
+      self.log.format = parse_var("LOG_FORMAT", &format)?;
+    }
+    if let Some(static_dir) = var("CLIENT_DIR") {
+      self.client.static_dir = Some(static_dir);
+    }
+    Ok(())
+  }
+
+  /// catches settings the server would only trip over once it's running
+  pub fn validate(&self) -> anyhow::Result<()> {
+    if self.server.host.trim().is_empty() {
+      bail!("server.host is empty");
+    }
+    if self.server.port == 0 {
+      bail!("server.port must not be 0");
+    }
+    match &self.storage.url {
+      Some(url) if !url.trim().is_empty() => {}
+      _ => bail!("storage.url is not set, set it in the config file or with DATABASE_URL"),
+    }
+    if self.storage.database.trim().is_empty() {
+      bail!("storage.database is empty");
+    }
+    if self.cors.allowed_origins.is_empty() {
+      bail!("cors.allowed_origins is empty, use \"*\" to allow any origin");
+    }
+    for origin in &self.cors.allowed_origins {
+      let is_url = origin.starts_with("http://") || origin.starts_with("https://");
+      if origin != "*" && !is_url {
+        bail!(
+          "cors origin {:?} must start with http:// or https://",
+          origin
+        );
+      }
+    }
+    if self.turns.timeout_secs == Some(0) {
+      bail!("turns.timeout_secs must be positive, leave it out to turn the timeout off");
+    }
+    if self.rooms.max_chat_length == 0 {
+      bail!("rooms.max_chat_length must be positive");
+    }
+    if self.rooms.chat_rate_limit == 0 || self.rooms.chat_rate_window_secs == 0 {
+      bail!("rooms.chat_rate_limit and rooms.chat_rate_window_secs must be positive");
+    }
+    if let Some(static_dir) = &self.client.static_dir {
+      if self.client.embedded {
+        bail!("client.static_dir and client.embedded can't be used together");
+      }
+      if !Path::new(static_dir).join("index.html").is_file() {
+        bail!(
+          "client.static_dir {} has no index.html, build the client first",
+          static_dir
+        );
+      }
+    }
+    if self.client.embedded && !cfg!(feature = "embed-client") {
+      bail!("client.embedded needs a server built with the embed-client feature");
+    }
+    Ok(())
+  }
+
+  pub fn bind_address(&self) -> String {
+    format!("{}:{}", self.server.host, self.server.port)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn with_url(mut config: ServerConfig) -> ServerConfig {
+    config.storage.url = Some("mongodb://localhost:27017".to_string());
+    config
+  }
+
+  #[test]
+  fn reads_sections_from_toml() {
+    let config = ServerConfig::from_toml(
+      r#"
+      [server]
+      port = 8000
+
+      [cors]
+      allowed_origins = ["https://ludo.example.com"]
+
+      [turns]
+      timeout_secs = 60
+
+      [log]
+      format = "json"
+      "#,
+    )
+    .unwrap();
+
+    assert_eq!(config.server.port, 8000);
+    assert_eq!(config.server.host, "0.0.0.0");
+    assert!(!config.cors.allows_any_origin());
+    assert_eq!(config.turns.timeout(), Some(Duration::from_secs(60)));
+    assert_eq!(config.log.format, LogFormat::Json);
+    assert_eq!(config.storage.database, "main");
+    assert!(ServerConfig::from_toml("[bots]\ndelay = 5").is_err());
+  }
+
+  #[test]
+  fn environment_overrides_the_file() {
+    let mut config = ServerConfig::from_toml("[server]\nport = 8000").unwrap();
+    config
+      .apply_env(|name| match name {
+        "DATABASE_URL" => Some("mongodb://db:27017".to_string()),
+        "PORT" => Some("9000".to_string()),
+        "CORS_ORIGINS" => Some("http://localhost:8080, https://ludo.example.com".to_string()),
+        _ => None,
+      })
+      .unwrap();
+
+    assert_eq!(config.storage.url.as_deref(), Some("mongodb://db:27017"));
+    assert_eq!(config.bind_address(), "0.0.0.0:9000");
+    assert_eq!(config.cors.allowed_origins.len(), 2);
+    assert!(config.validate().is_ok());
+    assert!(config
+      .apply_env(|name| (name == "PORT").then(|| "eighty".to_string()))
+      .is_err());
+  }
+
+  #[test]
+  fn rejects_invalid_settings() {
+    assert!(ServerConfig::default().validate().is_err());
+    assert!(with_url(ServerConfig::default()).validate().is_ok());
+
+    let mut config = with_url(ServerConfig::default());
+    config.cors.allowed_origins = vec!["ludo.example.com".to_string()];
+    assert!(config.validate().is_err());
+
+    let mut config = with_url(ServerConfig::default());
+    config.turns.timeout_secs = Some(0);
+    assert!(config.validate().is_err());
+
+    let mut config = with_url(ServerConfig::default());
+    config.client.static_dir = Some("no/such/dist".to_string());
+    assert!(config.validate().is_err());
+  }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use super::game::Game;
+
+/// the game right before the last move of a human player, the move can be taken back
+/// as long as nothing else happened in the game
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Snapshot {
+  pub game_id: String,
+  /// player who made the move, only they can ask to take it back
+  pub player_id: String,
+  /// sequence of the game right after the move, see `Game::sequence`
+  pub sequence: u64,
+  pub game: Game,
+  #[serde(default)]
+  pub undo_requested: bool,
+  /// players who agreed with the undo
+  #[serde(default)]
+  pub accepted: Vec<String>,
+}
@@ -0,0 +1,67 @@
+use actix_web::web::Bytes;
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::utils::enums::ServerMessage;
+
+/// encoding of websocket messages, chosen by the client when opening the websocket
+/// (`?encoding=msgpack`), JSON is the default since it's readable while debugging
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WireFormat {
+  #[default]
+  #[serde(rename = "json")]
+  Json,
+  #[serde(rename = "msgpack")]
+  MessagePack,
+}
+
+#[derive(Deserialize)]
+pub struct WireFormatQuery {
+  #[serde(default)]
+  pub encoding: WireFormat,
+}
+
+/// a `ServerMessage` serialized once for every wire format, so a broadcast isn't re-encoded
+/// for each session, sessions send the encoding their client asked for
+#[derive(Clone, Debug)]
+pub struct EncodedMessage {
+  /// see `ServerMessage::name`
+  pub name: &'static str,
+  pub json: Arc<str>,
+  /// `None` if the message couldn't be encoded, the JSON is sent instead
+  pub message_pack: Option<Bytes>,
+}
+
+impl EncodedMessage {
+  pub fn new(message: &ServerMessage) -> Self {
+    EncodedMessage {
+      name: message.name(),
+      json: serde_json::to_string(message).unwrap().into(),
+      message_pack: rmp_serde::to_vec_named(message).ok().map(Bytes::from),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::{color::Color, game::Game, player::Player};
+  use serde_json::Value;
+
+  #[test]
+  fn message_pack_matches_json() {
+    let mut game = Game::new();
+    game.players = vec![Player::new("".into(), "Alice".into(), Color::Red, true)];
+    for message in &[
+      ServerMessage::DiceValue(6, true),
+      ServerMessage::SkipPlayer,
+      ServerMessage::GameUpdate(game),
+      ServerMessage::UndoRequested(Color::Red),
+    ] {
+      let encoded = EncodedMessage::new(message);
+      let from_json: Value = serde_json::from_str(&encoded.json).unwrap();
+      let from_message_pack: Value = rmp_serde::from_slice(&encoded.message_pack.unwrap()).unwrap();
+      assert_eq!(from_json, from_message_pack);
+    }
+  }
+}
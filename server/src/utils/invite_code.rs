@@ -0,0 +1,24 @@
+use rand::Rng;
+
+const PREFIX: &str = "LUDO-";
+const CODE_LENGTH: usize = 4;
+// letters and digits which can't be confused with each other (no 0/O, 1/I/L)
+const ALPHABET: &[u8] = b"23456789ABCDEFGHJKMNPQRSTUVWXYZ";
+
+/// creates a short human-friendly code, e.g. LUDO-7K3P
+pub fn create_invite_code() -> String {
+  let mut rng = rand::thread_rng();
+  let code: String = (0..CODE_LENGTH)
+    .map(|_| ALPHABET[rng.gen_range(0..ALPHABET.len())] as char)
+    .collect();
+  format!("{}{}", PREFIX, code)
+}
+
+/// accepts codes typed in by hand - lowercase, with surrounding whitespace or without the prefix
+pub fn normalize_invite_code(code: &str) -> String {
+  let code = code.trim().to_uppercase();
+  match code.starts_with(PREFIX) {
+    true => code,
+    false => format!("{}{}", PREFIX, code),
+  }
+}
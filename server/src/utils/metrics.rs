@@ -0,0 +1,132 @@
+use prometheus::{
+  core::Collector, exponential_buckets, Encoder, Histogram, HistogramOpts, HistogramTimer,
+  HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder,
+};
+use std::sync::OnceLock;
+
+use super::enums::{ClientMessage, ErrorCode};
+use crate::models::wire_format::EncodedMessage;
+
+/// everything exposed on `/metrics`, the counters are shared by all actors and requests
+pub struct Metrics {
+  registry: Registry,
+  pub active_rooms: IntGauge,
+  pub sessions: IntGauge,
+  pub spectators: IntGauge,
+  pub messages_in: IntCounterVec,
+  pub messages_out: IntCounterVec,
+  pub storage_latency: HistogramVec,
+  pub bot_think_time: Histogram,
+  pub games_started: IntCounter,
+  pub games_finished: IntCounter,
+  pub errors: IntCounterVec,
+}
+
+impl Metrics {
+  fn new() -> prometheus::Result<Self> {
+    let registry = Registry::new_custom(Some("ludo".to_string()), None)?;
+    let metrics = Metrics {
+      active_rooms: IntGauge::new("active_rooms", "Rooms with at least one connected player")?,
+      sessions: IntGauge::new("sessions", "Connected game sessions, spectators included")?,
+      spectators: IntGauge::new("spectators", "Connected spectator sessions")?,
+      messages_in: IntCounterVec::new(
+        Opts::new("messages_in_total", "Commands received from clients"),
+        &["message"],
+      )?,
+      messages_out: IntCounterVec::new(
+        Opts::new("messages_out_total", "Messages sent to clients"),
+        &["message"],
+      )?,
+      storage_latency: HistogramVec::new(
+        HistogramOpts::new("storage_seconds", "Duration of database operations")
+          .buckets(exponential_buckets(0.001, 2.0, 14)?),
+        &["operation"],
+      )?,
+      bot_think_time: Histogram::with_opts(
+        HistogramOpts::new("bot_think_seconds", "Time bots take to choose a move")
+          .buckets(exponential_buckets(0.000_01, 4.0, 10)?),
+      )?,
+      games_started: IntCounter::new("games_started_total", "Games which have been started")?,
+      games_finished: IntCounter::new("games_finished_total", "Games which have a winner")?,
+      errors: IntCounterVec::new(
+        Opts::new("errors_total", "Errors sent to clients"),
+        &["code"],
+      )?,
+      registry,
+    };
+
+    let collectors: Vec<Box<dyn Collector>> = vec![
+      Box::new(metrics.active_rooms.clone()),
+      Box::new(metrics.sessions.clone()),
+      Box::new(metrics.spectators.clone()),
+      Box::new(metrics.messages_in.clone()),
+      Box::new(metrics.messages_out.clone()),
+      Box::new(metrics.storage_latency.clone()),
+      Box::new(metrics.bot_think_time.clone()),
+      Box::new(metrics.games_started.clone()),
+      Box::new(metrics.games_finished.clone()),
+      Box::new(metrics.errors.clone()),
+    ];
+    for collector in collectors {
+      metrics.registry.register(collector)?;
+    }
+    Ok(metrics)
+  }
+
+  /// the metrics in the Prometheus text format
+  pub fn render(&self) -> String {
+    let mut buffer = Vec::new();
+    let encoder = TextEncoder::new();
+    // only fails for invalid metrics, which are caught when they are registered
+    encoder.encode(&self.registry.gather(), &mut buffer).ok();
+    String::from_utf8(buffer).unwrap_or_default()
+  }
+
+  /// observes the latency of a database operation once the returned timer is dropped
+  pub fn storage_timer(&self, operation: &str) -> HistogramTimer {
+    self
+      .storage_latency
+      .with_label_values(&[operation])
+      .start_timer()
+  }
+
+  pub fn count_in(&self, message: &ClientMessage) {
+    self.messages_in.with_label_values(&[message.name()]).inc();
+  }
+
+  pub fn count_out(&self, message: &EncodedMessage) {
+    self.messages_out.with_label_values(&[message.name]).inc();
+  }
+
+  /// called when the error is sent, not when it's created
+  pub fn count_error(&self, code: &ErrorCode) {
+    self.errors.with_label_values(&[code.name()]).inc();
+  }
+}
+
+pub fn metrics() -> &'static Metrics {
+  static METRICS: OnceLock<Metrics> = OnceLock::new();
+  METRICS.get_or_init(|| Metrics::new().expect("metrics are valid"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::utils::enums::ServerMessage;
+
+  #[test]
+  fn labels_by_variant() {
+    assert_eq!(ClientMessage::MoveFigure(3).name(), "MoveFigure");
+    assert_eq!(
+      ErrorCode::MessageTooLong { max_length: 200 }.name(),
+      "MessageTooLong"
+    );
+
+    let metrics = Metrics::new().unwrap();
+    metrics.count_out(&EncodedMessage::new(&ServerMessage::DiceValue(6, true)));
+    metrics.count_in(&ClientMessage::ThrowDice);
+    let rendered = metrics.render();
+    assert!(rendered.contains("ludo_messages_out_total{message=\"DiceValue\"} 1"));
+    assert!(rendered.contains("ludo_messages_in_total{message=\"ThrowDice\"} 1"));
+  }
+}
@@ -1,5 +1,7 @@
-pub mod bot;
-pub mod dice;
 pub mod enums;
-pub mod game;
-pub mod player;
+pub mod invite_code;
+pub mod metrics;
+pub mod profanity;
+pub mod protocol;
+
+pub use engine::utils::{bot, dice, game};
@@ -0,0 +1,46 @@
+const BLOCKED_WORDS: [&str; 12] = [
+  "ass",
+  "asshole",
+  "bastard",
+  "bitch",
+  "bollocks",
+  "crap",
+  "cunt",
+  "dick",
+  "fuck",
+  "motherfucker",
+  "shit",
+  "wanker",
+];
+
+fn is_blocked(word: &str) -> bool {
+  let word = word.to_lowercase();
+  BLOCKED_WORDS.contains(&word.as_str())
+}
+
+/// replaces every blocked word with asterisks, punctuation and spacing are kept as they are
+pub fn filter_profanity(text: &str) -> String {
+  let mut filtered = String::with_capacity(text.len());
+  let mut word = String::new();
+
+  for character in text.chars() {
+    if character.is_alphanumeric() {
+      word.push(character);
+      continue;
+    }
+    push_word(&mut filtered, &word);
+    word.clear();
+    filtered.push(character);
+  }
+  push_word(&mut filtered, &word);
+
+  filtered
+}
+
+fn push_word(filtered: &mut String, word: &str) {
+  if is_blocked(word) {
+    filtered.extend(word.chars().map(|_| '*'));
+  } else {
+    filtered.push_str(word);
+  }
+}
@@ -0,0 +1,48 @@
+use serde_json::Value;
+
+use super::enums::{ClientMessage, ClientRequest, ErrorCode};
+
+/// longest frame accepted from a client, commands are much shorter
+pub const MAX_MESSAGE_SIZE: usize = 4096;
+
+type ParsedRequest = Result<(ClientMessage, Option<String>), ErrorCode>;
+
+/// parses a text frame into a command and its optional request id
+pub fn parse_client_request(text: &str) -> ParsedRequest {
+  check_size(text.len())?;
+  let value: Value = serde_json::from_str(text).map_err(|_| ErrorCode::MalformedMessage)?;
+  parse_value(value)
+}
+
+/// same as `parse_client_request` for MessagePack encoded binary frames
+pub fn parse_binary_client_request(bytes: &[u8]) -> ParsedRequest {
+  check_size(bytes.len())?;
+  let value: Value = rmp_serde::from_slice(bytes).map_err(|_| ErrorCode::MalformedMessage)?;
+  parse_value(value)
+}
+
+fn check_size(size: usize) -> Result<(), ErrorCode> {
+  if size > MAX_MESSAGE_SIZE {
+    return Err(ErrorCode::MessageTooLarge {
+      max_size: MAX_MESSAGE_SIZE,
+    });
+  }
+  Ok(())
+}
+
+fn parse_value(value: Value) -> ParsedRequest {
+  match serde_json::from_value::<ClientRequest>(value.clone()) {
+    Ok(request) => Ok(request.into_parts()),
+    Err(_) if is_unknown_command(&value) => Err(ErrorCode::UnknownCommand),
+    Err(_) => Err(ErrorCode::MalformedMessage),
+  }
+}
+
+/// well formed json, but the command itself doesn't exist
+fn is_unknown_command(value: &Value) -> bool {
+  let message = value.get("message").unwrap_or(value);
+  match serde_json::from_value::<ClientMessage>(message.clone()) {
+    Ok(_) => false,
+    Err(e) => e.to_string().starts_with("unknown variant"),
+  }
+}